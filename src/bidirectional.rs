@@ -0,0 +1,87 @@
+//! # bidirectional
+//!
+//! A wrapper holding two copies of a biquad sharing the same coefficients, one running forward in
+//! time and one running backward in time. The two copies have separate internal states, so a block
+//! can be filtered forward while another (or the same) block is filtered in reverse, which is the
+//! building block for zero-phase filtering and time-reversal effects.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//! let mut biquad = BidirectionalBiquad::new(DirectForm2Transposed::<f32>::new(coeffs));
+//!
+//! let input = [0.0, 1.0, 1.0, 1.0];
+//! let (forward, backward) = biquad.run_block_bidirectional(&input, &input);
+//! # }
+//! ```
+
+use crate::{Biquad, Coefficients};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A forward and a backward running biquad with separate states and shared coefficients
+#[derive(Copy, Clone, Debug)]
+pub struct BidirectionalBiquad<B> {
+    forward: B,
+    backward: B,
+}
+
+impl<B: Clone> BidirectionalBiquad<B> {
+    /// Creates the forward and backward biquads from a single biquad
+    pub fn new(biquad: B) -> Self {
+        BidirectionalBiquad {
+            forward: biquad.clone(),
+            backward: biquad,
+        }
+    }
+}
+
+impl<B> BidirectionalBiquad<B> {
+    /// Filters `forward` in order with the forward biquad and `backward` in reverse order with the
+    /// backward biquad. Both outputs are returned in the order of their inputs, i.e. the backward
+    /// output at index `i` corresponds to `backward[i]`. The states are kept between calls.
+    #[cfg(feature = "alloc")]
+    pub fn run_block_bidirectional<T: Copy>(
+        &mut self,
+        forward: &[T],
+        backward: &[T],
+    ) -> (Vec<T>, Vec<T>)
+    where
+        B: Biquad<T>,
+    {
+        let forward_out = forward.iter().map(|&x| self.forward.run(x)).collect();
+
+        let mut backward_out: Vec<T> = backward
+            .iter()
+            .rev()
+            .map(|&x| self.backward.run(x))
+            .collect();
+        backward_out.reverse();
+
+        (forward_out, backward_out)
+    }
+
+    /// Updating of coefficients of both biquads
+    pub fn update_coefficients<T: Copy>(&mut self, new_coefficients: Coefficients<T>)
+    where
+        B: Biquad<T>,
+    {
+        self.forward.update_coefficients(new_coefficients);
+        self.backward.update_coefficients(new_coefficients);
+    }
+
+    /// Set the internal state of both biquads to 0 without allocation
+    pub fn reset_state<T>(&mut self)
+    where
+        B: Biquad<T>,
+    {
+        self.forward.reset_state();
+        self.backward.reset_state();
+    }
+}
@@ -0,0 +1,108 @@
+//! # builder
+//!
+//! A builder for `Coefficients`, naming the parameters of `Coefficients::from_params` instead of
+//! passing them by position. The filter type, sample rate and cutoff frequency are required, while
+//! the Q value defaults to `Q_BUTTERWORTH_F64` and the gain of the filter type can be overridden
+//! by `gain_db`.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = CoefficientsBuilder::<f32>::new()
+//!     .filter_type(Type::PeakingEQ(0.0))
+//!     .sample_rate(48.khz())
+//!     .cutoff(1.khz())
+//!     .q(2.0)
+//!     .gain_db(-6.0)
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut biquad = DirectForm1::<f32>::new(coeffs);
+//! let output = biquad.run(1.0);
+//! ```
+
+use crate::{
+    float::Float, frequency::Hertz, gain::Decibels, Coefficients, Errors, Type, Q_BUTTERWORTH_F64,
+};
+
+/// Builder collecting the parameters of `Coefficients::from_params`, see `build`
+#[derive(Copy, Clone, Debug)]
+pub struct CoefficientsBuilder<T> {
+    filter: Option<Type<T>>,
+    fs: Option<Hertz<T>>,
+    f0: Option<Hertz<T>>,
+    q_value: Option<T>,
+    db_gain: Option<T>,
+}
+
+impl<T> Default for CoefficientsBuilder<T> {
+    fn default() -> Self {
+        CoefficientsBuilder {
+            filter: None,
+            fs: None,
+            f0: None,
+            q_value: None,
+            db_gain: None,
+        }
+    }
+}
+
+impl<T: Float> CoefficientsBuilder<T> {
+    /// Creates a builder without any parameters set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the filter type, this is required
+    pub fn filter_type(mut self, filter: Type<T>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets the sample rate, this is required
+    pub fn sample_rate(mut self, fs: Hertz<T>) -> Self {
+        self.fs = Some(fs);
+        self
+    }
+
+    /// Sets the cutoff or center frequency, this is required
+    pub fn cutoff(mut self, f0: Hertz<T>) -> Self {
+        self.f0 = Some(f0);
+        self
+    }
+
+    /// Sets the Q value, which defaults to `Q_BUTTERWORTH_F64`
+    pub fn q(mut self, q_value: T) -> Self {
+        self.q_value = Some(q_value);
+        self
+    }
+
+    /// Sets the gain in decibels of the filter types with a gain, overriding the gain of the
+    /// filter type. Has no effect on filter types without a gain.
+    pub fn gain_db(mut self, db_gain: impl Into<Decibels<T>>) -> Self {
+        self.db_gain = Some(db_gain.into().db());
+        self
+    }
+
+    /// Creates the coefficients with `Coefficients::from_params`. Results in an
+    /// `Err(Errors::MissingParameter)` if the filter type, sample rate or cutoff frequency is not
+    /// set, and in the errors of `from_params` otherwise.
+    pub fn build(&self) -> Result<Coefficients<T>, Errors> {
+        let (filter, fs, f0) = match (self.filter, self.fs, self.f0) {
+            (Some(filter), Some(fs), Some(f0)) => (filter, fs, f0),
+            _ => return Err(Errors::MissingParameter),
+        };
+
+        let filter = match self.db_gain {
+            Some(db_gain) => filter.map_gain(|_| db_gain),
+            None => filter,
+        };
+        let q_value = self
+            .q_value
+            .unwrap_or_else(|| T::from_f64(Q_BUTTERWORTH_F64));
+
+        Coefficients::<T>::from_params(filter, fs, f0, q_value)
+    }
+}
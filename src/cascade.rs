@@ -0,0 +1,333 @@
+//! # cascade
+//!
+//! A container for running several biquads in series, where the output of one stage is the input
+//! of the next. This is how higher order filters are realized from second order sections.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! let mut cascade = Cascade::new([
+//!     DirectForm1::<f32>::new(coeffs),
+//!     DirectForm1::<f32>::new(coeffs),
+//! ]);
+//!
+//! let output = cascade.run(1.0);
+//! ```
+
+use crate::{Biquad, Coefficients, DirectForm1, DirectForm2Transposed, Errors};
+use core::convert::TryFrom;
+
+use crate::float::Float;
+
+#[cfg(feature = "alloc")]
+use crate::frequency::Hertz;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Version of the binary encoding of a cascade
+const ENCODING_VERSION: u8 = 1;
+
+/// Length of the header of the binary encoding, holding the version, float size and stage count
+const ENCODING_HEADER_LEN: usize = 4;
+
+/// A biquad stage which is fully described by its coefficients, used for creating and encoding
+/// cascades from coefficients alone
+pub trait Section {
+    /// The sample type of the stage
+    type Sample;
+
+    /// Creates the stage from its coefficients, with a zeroed state
+    fn from_coefficients(coefficients: Coefficients<Self::Sample>) -> Self;
+
+    /// The current coefficients of the stage
+    fn coefficients(&self) -> Coefficients<Self::Sample>;
+}
+
+/// Biquads running in series, every stage keeps its own coefficients and state
+#[derive(Copy, Clone, Debug)]
+pub struct Cascade<B, const N: usize> {
+    stages: [B; N],
+}
+
+impl<B, const N: usize> Cascade<B, N> {
+    /// Creates a cascade from its stages, the first stage is applied first
+    pub fn new(stages: [B; N]) -> Self {
+        Cascade { stages }
+    }
+
+    /// A single iteration of the cascade, running the input through all stages in order
+    pub fn run<T>(&mut self, input: T) -> T
+    where
+        B: Biquad<T>,
+    {
+        self.stages
+            .iter_mut()
+            .fold(input, |sample, stage| stage.run(sample))
+    }
+
+    /// A single iteration of the cascade running the input through `stage` only, bypassing the
+    /// other stages, e.g. for auditioning a single band of an EQ. The bypassed stages are not run
+    /// and keep their state. Panics if `stage >= N`.
+    pub fn run_solo<T>(&mut self, input: T, stage: usize) -> T
+    where
+        B: Biquad<T>,
+    {
+        self.stages[stage].run(input)
+    }
+
+    /// Set the internal state of all stages to 0 without allocation
+    pub fn reset_state<T>(&mut self)
+    where
+        B: Biquad<T>,
+    {
+        for stage in self.stages.iter_mut() {
+            stage.reset_state();
+        }
+    }
+
+    /// The processing latency of the cascade in samples, the sum of the `latency_samples` of all
+    /// stages, for delay compensation by a host
+    pub fn total_latency_samples<T>(&self) -> usize
+    where
+        B: Biquad<T>,
+    {
+        self.stages
+            .iter()
+            .map(|stage| stage.latency_samples())
+            .sum()
+    }
+
+    /// The stages of the cascade
+    pub fn stages(&self) -> &[B; N] {
+        &self.stages
+    }
+
+    /// The stages of the cascade, for changing a single stage
+    pub fn stages_mut(&mut self) -> &mut [B; N] {
+        &mut self.stages
+    }
+
+    /// Filters `input` into `output` through all stages, keeping the states across the block.
+    /// Panics if the lengths differ.
+    pub fn process_block<T>(&mut self, input: &[T], output: &mut [T])
+    where
+        B: Biquad<T>,
+        T: Copy,
+    {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "output length must match the input length"
+        );
+
+        for (out, &x) in output.iter_mut().zip(input) {
+            *out = self.run(x);
+        }
+    }
+
+    /// Filters `buf` in place through all stages, keeping the states across the block
+    pub fn process_block_in_place<T>(&mut self, buf: &mut [T])
+    where
+        B: Biquad<T>,
+        T: Copy,
+    {
+        for sample in buf.iter_mut() {
+            *sample = self.run(*sample);
+        }
+    }
+
+    /// Splits the stages into the first `k` stages and the remaining ones, e.g. to tap the signal
+    /// after stage `k` when running the two parts separately. Panics if `k > N`.
+    pub fn split_at(&mut self, k: usize) -> (&mut [B], &mut [B]) {
+        self.stages.split_at_mut(k)
+    }
+}
+
+impl<T, const N: usize> Cascade<DirectForm1<T>, N> {
+    /// Iterates mutably over the coefficients of all stages, keeping the internal states
+    pub fn coefficients_iter_mut(&mut self) -> impl Iterator<Item = &mut Coefficients<T>> {
+        self.stages.iter_mut().map(|stage| &mut stage.coeffs)
+    }
+}
+
+impl<T, const N: usize> Cascade<DirectForm2Transposed<T>, N> {
+    /// Iterates mutably over the coefficients of all stages, keeping the internal states
+    pub fn coefficients_iter_mut(&mut self) -> impl Iterator<Item = &mut Coefficients<T>> {
+        self.stages.iter_mut().map(|stage| &mut stage.coeffs)
+    }
+}
+
+impl<B: Section, const N: usize> Cascade<B, N> {
+    /// Creates a cascade with one stage per set of coefficients, with zeroed states, e.g. from the
+    /// second order sections of a higher order design
+    pub fn from_coefficients(coefficients: [Coefficients<B::Sample>; N]) -> Self {
+        Cascade::new(coefficients.map(B::from_coefficients))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: Section, const N: usize> Cascade<B, N>
+where
+    B::Sample: Float,
+{
+    /// Creates a cascade of an `order`-th order Bessel low pass with its -3 dB point at `f0`, from
+    /// the sections of `Coefficients::bessel_lowpass_sections`, resulting in the same errors.
+    /// Results in an `Err(Errors::StageCountMismatch)` if `N` is not the number of sections,
+    /// `(order + 1) / 2`.
+    pub fn bessel_lowpass(
+        order: usize,
+        fs: Hertz<B::Sample>,
+        f0: Hertz<B::Sample>,
+    ) -> Result<Self, Errors> {
+        let sections = Coefficients::<B::Sample>::bessel_lowpass_sections(order, fs, f0)?;
+
+        Self::from_sections(&sections)
+    }
+
+    /// Creates a cascade of an `order`-th order Chebyshev type I low pass with a passband ripple
+    /// of `ripple_db`, from the sections of `Coefficients::chebyshev1_lowpass`, resulting in the
+    /// same errors. Results in an `Err(Errors::StageCountMismatch)` if `N` is not the number of
+    /// sections, `(order + 1) / 2`.
+    pub fn chebyshev1_lowpass(
+        order: usize,
+        fs: Hertz<B::Sample>,
+        f0: Hertz<B::Sample>,
+        ripple_db: B::Sample,
+    ) -> Result<Self, Errors> {
+        let sections = Coefficients::<B::Sample>::chebyshev1_lowpass(order, fs, f0, ripple_db)?;
+
+        Self::from_sections(&sections)
+    }
+
+    /// Creates a cascade of an `order`-th order Chebyshev type I high pass with a passband ripple
+    /// of `ripple_db`, from the sections of `Coefficients::chebyshev1_highpass`, resulting in the
+    /// same errors. Results in an `Err(Errors::StageCountMismatch)` if `N` is not the number of
+    /// sections, `(order + 1) / 2`.
+    pub fn chebyshev1_highpass(
+        order: usize,
+        fs: Hertz<B::Sample>,
+        f0: Hertz<B::Sample>,
+        ripple_db: B::Sample,
+    ) -> Result<Self, Errors> {
+        let sections = Coefficients::<B::Sample>::chebyshev1_highpass(order, fs, f0, ripple_db)?;
+
+        Self::from_sections(&sections)
+    }
+
+    fn from_sections(sections: &[Coefficients<B::Sample>]) -> Result<Self, Errors> {
+        if sections.len() != N {
+            return Err(Errors::StageCountMismatch);
+        }
+
+        Ok(Cascade::from_coefficients(core::array::from_fn(|stage| {
+            sections[stage]
+        })))
+    }
+}
+
+impl<B: Section, const N: usize> Cascade<B, N>
+where
+    B::Sample: Float,
+{
+    /// The length in bytes of the binary encoding of the cascade
+    pub const ENCODED_LEN: usize = ENCODING_HEADER_LEN + N * 5 * B::Sample::SIZE;
+
+    /// Writes the coefficients of all stages in a compact binary format to the start of `buf`,
+    /// returning the number of bytes written, see `ENCODED_LEN`. The encoding starts with a header
+    /// of the format version, the float size in bytes and the stage count as a little endian
+    /// `u16`, followed by `b0`, `b1`, `b2`, `a1` and `a2` of every stage as little endian floats.
+    /// Panics if `buf` is shorter than `ENCODED_LEN` or if there are more than `u16::MAX` stages.
+    pub fn write_bytes(&self, buf: &mut [u8]) -> usize {
+        let size = B::Sample::SIZE;
+        let buf = &mut buf[..Self::ENCODED_LEN];
+        let count = u16::try_from(N).expect("too many stages to encode");
+
+        buf[0] = ENCODING_VERSION;
+        buf[1] = size as u8;
+        buf[2..ENCODING_HEADER_LEN].copy_from_slice(&count.to_le_bytes());
+
+        let values = self.stages.iter().flat_map(|stage| {
+            let c = stage.coefficients();
+            [c.b0, c.b1, c.b2, c.a1, c.a2]
+        });
+        for (chunk, value) in buf[ENCODING_HEADER_LEN..]
+            .chunks_exact_mut(size)
+            .zip(values)
+        {
+            value.write_le(chunk);
+        }
+
+        Self::ENCODED_LEN
+    }
+
+    /// The coefficients of all stages in the binary format of `write_bytes`
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = alloc::vec![0; Self::ENCODED_LEN];
+        self.write_bytes(&mut buf);
+        buf
+    }
+
+    /// Creates a cascade from the binary format of `write_bytes`, with zeroed states. Results in
+    /// an `Err(Errors::InvalidEncoding)` if the version, float size, stage count or length of
+    /// `bytes` do not match.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Errors> {
+        let size = B::Sample::SIZE;
+        if bytes.len() != Self::ENCODED_LEN
+            || bytes[0] != ENCODING_VERSION
+            || usize::from(bytes[1]) != size
+            || usize::from(u16::from_le_bytes([bytes[2], bytes[3]])) != N
+        {
+            return Err(Errors::InvalidEncoding);
+        }
+
+        let value = |i: usize| {
+            let start = ENCODING_HEADER_LEN + i * size;
+            B::Sample::read_le(&bytes[start..start + size])
+        };
+
+        Ok(Cascade::new(core::array::from_fn(|stage| {
+            B::from_coefficients(Coefficients {
+                b0: value(stage * 5),
+                b1: value(stage * 5 + 1),
+                b2: value(stage * 5 + 2),
+                a1: value(stage * 5 + 3),
+                a2: value(stage * 5 + 4),
+            })
+        })))
+    }
+}
+
+impl<T: Float> Section for DirectForm1<T> {
+    type Sample = T;
+
+    fn from_coefficients(coefficients: Coefficients<T>) -> Self {
+        DirectForm1 {
+            y1: T::ZERO,
+            y2: T::ZERO,
+            x1: T::ZERO,
+            x2: T::ZERO,
+            coeffs: coefficients,
+        }
+    }
+
+    fn coefficients(&self) -> Coefficients<T> {
+        self.coeffs
+    }
+}
+
+impl<T: Float> Section for DirectForm2Transposed<T> {
+    type Sample = T;
+
+    fn from_coefficients(coefficients: Coefficients<T>) -> Self {
+        DirectForm2Transposed::<T>::new(coefficients)
+    }
+
+    fn coefficients(&self) -> Coefficients<T> {
+        self.coeffs
+    }
+}
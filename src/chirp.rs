@@ -0,0 +1,67 @@
+//! # chirp
+//!
+//! Generation of exponential sine sweeps, or chirps, as test signals for measuring the response
+//! of a filter. An exponential sweep spends the same time in every octave, which matches the
+//! logarithmic frequency axis filter responses are usually read on. Requires the `alloc` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let fs = 48.khz();
+//! let chirp = generate_chirp::<f32>(fs, 20.hz(), 20.khz(), 48_000).unwrap();
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+//! let mut biquad = DirectForm1::<f32>::new(coeffs);
+//!
+//! let mut response = vec![0.0; chirp.len()];
+//! biquad.process_block(&chirp, &mut response);
+//! ```
+
+use crate::{float::Float, frequency::Hertz, Errors};
+use alloc::vec::Vec;
+use libm::{exp, log, sin};
+
+/// Generates `len` samples of a unit amplitude exponential sine sweep, whose instantaneous
+/// frequency rises or falls from `f_start` at the first sample to `f_end` at the last sample. The
+/// phase starts at 0. Results in an `Err(Errors::OutsideNyquist)` if either frequency is above
+/// half the sampling frequency.
+///
+/// # Panics
+///
+/// Panics if `f_start` or `f_end` is not positive.
+pub fn generate_chirp<T: Float>(
+    fs: Hertz<T>,
+    f_start: Hertz<T>,
+    f_end: Hertz<T>,
+    len: usize,
+) -> Result<Vec<T>, Errors> {
+    assert!(
+        f_start.hz() > T::ZERO && f_end.hz() > T::ZERO,
+        "the frequencies of a chirp must be positive"
+    );
+    if T::TWO * f_start.hz() > fs.hz() || T::TWO * f_end.hz() > fs.hz() {
+        return Err(Errors::OutsideNyquist);
+    }
+
+    // The phase is computed in f64, so that it stays accurate for long sweeps
+    let (fs, f_start, f_end) = (fs.hz().to_f64(), f_start.hz().to_f64(), f_end.hz().to_f64());
+    let two_pi = 2.0 * core::f64::consts::PI;
+    let duration = len.saturating_sub(1) as f64 / fs;
+    let ln_ratio = log(f_end / f_start);
+
+    Ok((0..len)
+        .map(|n| {
+            let t = n as f64 / fs;
+            // The integral of f(t) = f_start (f_end / f_start)^(t / duration)
+            let phase = if ln_ratio == 0.0 || duration == 0.0 {
+                two_pi * f_start * t
+            } else {
+                two_pi * f_start * duration / ln_ratio * (exp(ln_ratio * t / duration) - 1.0)
+            };
+
+            T::from_f64(sin(phase))
+        })
+        .collect())
+}
@@ -17,18 +17,16 @@
 //! # Examples
 //!
 //! ```
-//! fn main() {
-//!     use biquad::*;
+//! use biquad::*;
 //!
-//!     // Cutoff frequency
-//!     let f0 = 10.hz();
+//! // Cutoff frequency
+//! let f0 = 10.hz();
 //!
-//!     // Sampling frequency
-//!     let fs = 1.khz();
+//! // Sampling frequency
+//! let fs = 1.khz();
 //!
-//!     // Create coefficients
-//!     let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32);
-//! }
+//! // Create coefficients
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32);
 //! ```
 //!
 //! # Errors
@@ -36,20 +34,123 @@
 //! `Coefficients::from_params(...)` can error if the cutoff frequency does not adhere to the
 //! [Nyquist Frequency](https://en.wikipedia.org/wiki/Nyquist_frequency), or if the Q value is
 //! negative.
+//!
+//! `Coefficients::try_from_params(...)` additionally errors if the Q value is too small for the
+//! filter type, where `from_params(...)` would return non-finite or degenerate coefficients.
 
-use crate::{frequency::Hertz, Errors};
+use crate::{
+    float::Float, frequency::Hertz, gain::Decibels, Biquad, DirectForm2Transposed, Errors,
+};
+use core::ops::{Div, Mul};
 
-// For some reason this is not detected properly
-use libm::{tan, sin, cos, pow, tanf, sinf, cosf, powf, sqrt, sqrtf};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Common Q value of the Butterworth low-pass filter
 pub const Q_BUTTERWORTH_F32: f32 = core::f32::consts::FRAC_1_SQRT_2;
 pub const Q_BUTTERWORTH_F64: f64 = core::f64::consts::FRAC_1_SQRT_2;
 
+/// Q value of the second order Bessel low-pass filter, `1 / sqrt(3)`
+pub const Q_BESSEL_F32: f32 = 0.577_350_26;
+pub const Q_BESSEL_F64: f64 = 0.577_350_269_189_625_8;
+
+/// The analog sections of a low pass, as the frequencies relative to the cutoff and Qs of the
+/// second order sections, followed by the relative frequency of the single pole section of odd
+/// orders
+#[cfg(feature = "alloc")]
+type AnalogSections = (&'static [(f64, f64)], Option<f64>);
+
+/// The analog sections of the Bessel low passes of order 1 to 8, relative to the -3 dB cutoff,
+/// with the second order sections sorted by ascending Q
+#[cfg(feature = "alloc")]
+const BESSEL_SECTIONS: [AnalogSections; 8] = [
+    (&[], Some(1.0)),
+    (&[(1.272019649514069, 0.5773502691896257)], None),
+    (
+        &[(1.4476171331469874, 0.6910466258250713)],
+        Some(1.3226757999104448),
+    ),
+    (
+        &[
+            (1.4301715599939904, 0.5219345816689801),
+            (1.603357516216973, 0.8055382818416658),
+        ],
+        None,
+    ),
+    (
+        &[
+            (1.5563471222969236, 0.5635356208514565),
+            (1.7553777766370968, 0.9164773739482478),
+        ],
+        Some(1.502316271447479),
+    ),
+    (
+        &[
+            (1.6039191287737986, 0.51031782474877),
+            (1.6891682676204605, 0.6111945468780026),
+            (1.9047076123027624, 1.023313953826724),
+        ],
+        None,
+    ),
+    (
+        &[
+            (1.7163560448708646, 0.5323556978995465),
+            (1.8224174788579959, 0.6608213892970801),
+            (2.0494909002691073, 1.126257541983041),
+        ],
+        Some(1.6843681792731802),
+    ),
+    (
+        &[
+            (1.778465911774649, 0.50599106939747),
+            (1.8320926011985827, 0.5596091647957911),
+            (1.9531957590220648, 0.710852074441698),
+            (2.188726230527439, 1.2256694254081706),
+        ],
+        None,
+    ),
+];
+
+/// Smallest Q value accepted by `Coefficients::try_from_params` for both `f32` and `f64`, smaller
+/// values make the bandwidth so large that `alpha = sin(w0) / (2 Q)` dominates the coefficients
+pub const Q_MIN_F64: f64 = 1e-3;
+
+/// Converts a Q value to the damping ratio used in control theory, `zeta = 1 / (2 Q)`
+pub fn q_to_damping<T>(q: T) -> T
+where
+    T: From<u8> + Mul<Output = T> + Div<Output = T>,
+{
+    T::from(1) / (T::from(2) * q)
+}
+
+/// Converts a damping ratio to a Q value, `Q = 1 / (2 zeta)`
+pub fn damping_to_q<T>(zeta: T) -> T
+where
+    T: From<u8> + Mul<Output = T> + Div<Output = T>,
+{
+    T::from(1) / (T::from(2) * zeta)
+}
+
+/// The frequency whose prewarped analog frequency is `ratio` times the one of `f0`, for placing a
+/// section of an analog prototype with a cutoff of 1 rad/s
+#[cfg(feature = "alloc")]
+fn prewarped_frequency<T: Float>(fs: Hertz<T>, f0: Hertz<T>, ratio: T) -> Result<Hertz<T>, Errors> {
+    let omega = T::atan2(ratio * T::tan(T::PI * f0.hz() / fs.hz()), T::ONE);
+
+    Hertz::<T>::from_hz(omega * fs.hz() / T::PI)
+}
+
 /// The supported types of biquad coefficients. Note that single pole low pass filters are faster to
 /// retune, as all other filter types require evaluations of sin/cos functions
-/// The `LowShelf`, `HighShelf`, and `PeakingEQ` all have a gain value for its
+/// The `LowShelf`, `HighShelf`, `PeakingEQ` and `ConstantQPeaking` all have a gain value for its
 /// field, and represents the gain, in decibels, that the filter provides.
+/// `PeakingEQ` is the peaking (bell) filter of the Audio EQ Cookbook, whose resonance narrows as
+/// the gain increases, the Q of its poles being `A Q` for a boost. `ConstantQPeaking` keeps the
+/// resonance at the bandwidth `f0 / Q` for any gain, as expected from the bands of a graphic EQ,
+/// where neighboring bands should overlap the same way at every setting. For both, a cut is the
+/// exact inverse of the boost with the same magnitude.
+/// `Through` is a no-op filter which passes the signal unchanged, e.g. for disabled slots in a
+/// filter chain.
 #[derive(Clone, Copy, Debug)]
 pub enum Type<DBGain> {
     SinglePoleLowPassApprox,
@@ -62,10 +163,69 @@ pub enum Type<DBGain> {
     LowShelf(DBGain),
     HighShelf(DBGain),
     PeakingEQ(DBGain),
+    ConstantQPeaking(DBGain),
+    Through,
 }
 
-/// Holder of the biquad coefficients, utilizes normalized form
-#[derive(Clone, Copy, Debug)]
+impl<DBGain> Type<DBGain> {
+    /// The number of transcendental function evaluations (`sin`, `cos`, `tan` and `pow`) which
+    /// `Coefficients::from_params` performs for the type, e.g. for budgeting the cost of retuning
+    /// on a microcontroller. Shelf and peaking filters with a gain of 0 dB skip all of them.
+    pub fn trig_ops(&self) -> u8 {
+        match self {
+            Type::SinglePoleLowPassApprox | Type::Through => 0,
+            Type::SinglePoleLowPass => 1,
+            Type::LowPass | Type::HighPass | Type::BandPass | Type::Notch | Type::AllPass => 2,
+            Type::LowShelf(_)
+            | Type::HighShelf(_)
+            | Type::PeakingEQ(_)
+            | Type::ConstantQPeaking(_) => 3,
+        }
+    }
+
+    /// The same filter type with the gain converted by `f`
+    pub(crate) fn map_gain<T>(self, f: impl FnOnce(DBGain) -> T) -> Type<T> {
+        match self {
+            Type::SinglePoleLowPassApprox => Type::SinglePoleLowPassApprox,
+            Type::SinglePoleLowPass => Type::SinglePoleLowPass,
+            Type::LowPass => Type::LowPass,
+            Type::HighPass => Type::HighPass,
+            Type::BandPass => Type::BandPass,
+            Type::Notch => Type::Notch,
+            Type::AllPass => Type::AllPass,
+            Type::LowShelf(db_gain) => Type::LowShelf(f(db_gain)),
+            Type::HighShelf(db_gain) => Type::HighShelf(f(db_gain)),
+            Type::PeakingEQ(db_gain) => Type::PeakingEQ(f(db_gain)),
+            Type::ConstantQPeaking(db_gain) => Type::ConstantQPeaking(f(db_gain)),
+            Type::Through => Type::Through,
+        }
+    }
+}
+
+/// The numerator used when placing the poles directly with `Coefficients::from_pole`. The
+/// `Resonator` has zeros at DC and Nyquist with a gain of approximately 1 at the pole angle, while
+/// the `AllPass` mirrors the denominator for a flat magnitude response.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumeratorKind {
+    Resonator,
+    AllPass,
+}
+
+/// The bandwidth parameter of a design, in any of the forms of the Audio EQ Cookbook. `Q` is used
+/// as is, `BandwidthOctaves` is the bandwidth in octaves between the -3 dB points of a band pass
+/// or notch, respectively the midpoint gain frequencies of a peaking filter, and `ShelfSlope` is
+/// the shelf slope `S` of a shelf, where `S = 1` is the steepest monotonic slope.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BandwidthParam<T> {
+    Q(T),
+    BandwidthOctaves(T),
+    ShelfSlope(T),
+}
+
+/// Holder of the biquad coefficients, utilizes normalized form. The layout is that of
+/// `[T; 5]` in the order `a1`, `a2`, `b0`, `b1`, `b2`, see `Coefficients::as_array_ref`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Coefficients<T> {
     // Denominator coefficients
     pub a1: T,
@@ -77,440 +237,1691 @@ pub struct Coefficients<T> {
     pub b2: T,
 }
 
-impl Coefficients<f32> {
+impl<T: Float> Coefficients<T> {
+    /// Coefficients passing the signal unchanged
+    const IDENTITY: Coefficients<T> = Coefficients {
+        a1: T::ZERO,
+        a2: T::ZERO,
+        b0: T::ONE,
+        b1: T::ZERO,
+        b2: T::ZERO,
+    };
+
     /// Creates coefficients based on the biquad filter type, sampling and cutoff frequency, and Q
     /// value. Note that the cutoff frequency must be smaller than half the sampling frequency and
     /// that Q may not be negative, this will result in an `Err()`.
+    /// Shelf and peaking filters with a gain of 0 dB result in identity coefficients.
+    /// The design is computed in double precision and only the final coefficients are rounded to
+    /// `T`, which avoids the loss of accuracy at low cutoff frequencies, see `from_params_fast`.
     pub fn from_params(
-        filter: Type<f32>,
-        fs: Hertz<f32>,
-        f0: Hertz<f32>,
-        q_value: f32,
-    ) -> Result<Coefficients<f32>, Errors> {
-        if 2.0 * f0.hz() > fs.hz() {
-            return Err(Errors::OutsideNyquist);
-        }
-
-        if q_value < 0.0 {
-            return Err(Errors::NegativeQ);
-        }
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        let coeffs = Coefficients::<f64>::from_params_fast(
+            filter.map_gain(T::to_f64),
+            Hertz::<f64>::from_hz(fs.hz().to_f64())?,
+            Hertz::<f64>::from_hz(f0.hz().to_f64())?,
+            q_value.to_f64(),
+        )?;
 
-        let omega = 2.0 * core::f32::consts::PI * f0.hz() / fs.hz();
+        Ok(Coefficients {
+            a1: T::from_f64(coeffs.a1),
+            a2: T::from_f64(coeffs.a2),
+            b0: T::from_f64(coeffs.b0),
+            b1: T::from_f64(coeffs.b1),
+            b2: T::from_f64(coeffs.b2),
+        })
+    }
 
-        match filter {
-            Type::SinglePoleLowPassApprox => {
-                let alpha = omega / (omega + 1.0);
+    /// Creates coefficients the same way as `from_params`, with the numerator scaled to a gain of
+    /// exactly 0 dB at the reference frequency of the filter type, e.g. for gain matched A/B
+    /// comparisons. The reference is the center frequency `f0` for band pass and peaking filters,
+    /// the shelf for shelving filters, i.e. DC for the low shelf and Nyquist for the high shelf,
+    /// DC for the low pass, notch and all pass filters and Nyquist for the high pass filter. The
+    /// gain of a band pass at `f0` is its Q, while a peaking or shelving filter turns into a cut by
+    /// its gain elsewhere. Results in the same errors as `from_params`, and in an
+    /// `Err(Errors::GainUnreachable)` if the gain at the reference frequency is 0 or not finite,
+    /// so that no scaling reaches 0 dB.
+    pub fn from_params_normalized(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        let coeffs = Coefficients::<T>::from_params(filter, fs, f0, q_value)?;
 
-                Ok(Coefficients {
-                    a1: alpha - 1.0,
-                    a2: 0.0,
-                    b0: alpha,
-                    b1: 0.0,
-                    b2: 0.0,
-                })
-            }
-            Type::SinglePoleLowPass => {
-                let omega_t = tanf(omega / 2.0);
-                let a0 = 1.0 + omega_t;
-
-                Ok(Coefficients {
-                    a1: (omega_t - 1.0) / a0,
-                    a2: 0.0,
-                    b0: omega_t / a0,
-                    b1: omega_t / a0,
-                    b2: 0.0,
-                })
-            }
-            Type::LowPass => {
-                // The code for omega_s/c and alpha is currently duplicated due to the single pole
-                // low pass filter not needing it and when creating coefficients are commonly
-                // assumed to be of low computational complexity.
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = (1.0 - omega_c) * 0.5;
-                let b1 = 1.0 - omega_c;
-                let b2 = (1.0 - omega_c) * 0.5;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
-            Type::HighPass => {
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = (1.0 + omega_c) * 0.5;
-                let b1 = -(1.0 + omega_c);
-                let b2 = (1.0 + omega_c) * 0.5;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
+        let gain = match filter {
+            Type::BandPass | Type::PeakingEQ(_) | Type::ConstantQPeaking(_) => {
+                let (re, im) = coeffs.response_at(T::TWO * T::PI * f0.hz() / fs.hz());
+                T::hypot(re, im)
             }
-            Type::BandPass => {
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
+            Type::HighPass | Type::HighShelf(_) => T::fabs(coeffs.gain_at_nyquist()),
+            Type::SinglePoleLowPassApprox
+            | Type::SinglePoleLowPass
+            | Type::LowPass
+            | Type::Notch
+            | Type::AllPass
+            | Type::LowShelf(_)
+            | Type::Through => T::fabs(coeffs.gain_at_dc()),
+        };
 
-                let b0 = omega_s / 2.0;
-                let b1 = 0.;
-                let b2 = -(omega_s / 2.0);
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
+        // E.g. the numerator of a high pass at Nyquist is all zeros
+        if gain == T::ZERO || !gain.is_finite() {
+            return Err(Errors::GainUnreachable);
+        }
 
-                let div = 1.0 / a0;
+        Ok(Coefficients {
+            b0: coeffs.b0 / gain,
+            b1: coeffs.b1 / gain,
+            b2: coeffs.b2 / gain,
+            ..coeffs
+        })
+    }
 
-                Ok(Coefficients {
-                    a1: a1 * div,
-                    a2: a2 * div,
-                    b0: b0 * div,
-                    b1: b1 * div,
-                    b2: b2 * div,
-                })
-            }
-            Type::Notch => {
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = 1.0;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
-            Type::AllPass => {
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = 1.0 - alpha;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0 + alpha;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
-            Type::LowShelf(db_gain) => {
-                let a = powf(10.0f32,db_gain / 40.0);
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = a * ((a + 1.0) - (a - 1.0) * omega_c + 2.0 * alpha * sqrtf(a));
-                let b1 = 2.0 * a * ((a - 1.0) - (a + 1.0) * omega_c);
-                let b2 = a * ((a + 1.0) - (a - 1.0) * omega_c - 2.0 * alpha * sqrtf(a));
-                let a0 = (a + 1.0) + (a - 1.0) * omega_c + 2.0 * alpha * sqrtf(a);
-                let a1 = -2.0 * ((a - 1.0) + (a + 1.0) * omega_c);
-                let a2 = (a + 1.0) + (a - 1.0) * omega_c - 2.0 * alpha * sqrtf(a);
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
-            Type::HighShelf(db_gain) => {
-                let a = powf(10.0f32,db_gain / 40.0);
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = a * ((a + 1.0) + (a - 1.0) * omega_c + 2.0 * alpha * sqrtf(a));
-                let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * omega_c);
-                let b2 = a * ((a + 1.0) + (a - 1.0) * omega_c - 2.0 * alpha * sqrtf(a));
-                let a0 = (a + 1.0) - (a - 1.0) * omega_c + 2.0 * alpha * sqrtf(a);
-                let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * omega_c);
-                let a2 = (a + 1.0) - (a - 1.0) * omega_c - 2.0 * alpha * sqrtf(a);
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
-            Type::PeakingEQ(db_gain) => {
-                let a = powf(10.0f32,db_gain / 40.0);
-                let omega_s = sinf(omega);
-                let omega_c = cosf(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = 1.0 + alpha * a;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0 - alpha * a;
-                let a0 = 1.0 + alpha / a;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha / a;
-
-                Ok(Coefficients {
-                    a1: a1 / a0,
-                    a2: a2 / a0,
-                    b0: b0 / a0,
-                    b1: b1 / a0,
-                    b2: b2 / a0,
-                })
-            }
+    /// Creates coefficients the same way as `from_params`, but computed in the precision of `T`
+    /// throughout. For `f32` this is faster, at the cost of accuracy for cutoff frequencies far
+    /// below the sampling frequency.
+    pub fn from_params_fast(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        // A through filter passes the signal unchanged, independent of the other parameters
+        if let Type::Through = filter {
+            return Ok(Coefficients::<T>::IDENTITY);
         }
-    }
-}
 
-impl Coefficients<f64> {
-    /// Creates coefficients based on the biquad filter type, sampling and cutoff frequency, and Q
-    /// value. Note that the cutoff frequency must be smaller than half the sampling frequency and
-    /// that Q may not be negative, this will result in an `Err()`.
-    pub fn from_params(
-        filter: Type<f64>,
-        fs: Hertz<f64>,
-        f0: Hertz<f64>,
-        q_value: f64,
-    ) -> Result<Coefficients<f64>, Errors> {
-        if 2.0 * f0.hz() > fs.hz() {
+        if T::TWO * f0.hz() > fs.hz() {
             return Err(Errors::OutsideNyquist);
         }
 
-        if q_value < 0.0 {
+        if q_value < T::ZERO {
             return Err(Errors::NegativeQ);
         }
 
-        let omega = 2.0 * core::f64::consts::PI * f0.hz() / fs.hz();
+        Ok(Coefficients::<T>::from_params_unchecked(
+            filter, fs, f0, q_value,
+        ))
+    }
+
+    /// Creates coefficients the same way as `from_params_fast`, but without validating the
+    /// parameters, for hot paths where they have already been checked. The caller must ensure that
+    /// `f0` is below Nyquist and that Q is not negative, otherwise the coefficients are meaningless
+    /// and potentially unstable.
+    pub fn from_params_unchecked(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Coefficients<T> {
+        // A shelf or peaking filter without gain is exactly an identity filter
+        if let Type::LowShelf(db_gain)
+        | Type::HighShelf(db_gain)
+        | Type::PeakingEQ(db_gain)
+        | Type::ConstantQPeaking(db_gain) = filter
+        {
+            if db_gain == T::ZERO {
+                return Coefficients::<T>::IDENTITY;
+            }
+        }
+
+        let omega = T::TWO * T::PI * f0.hz() / fs.hz();
 
         match filter {
             Type::SinglePoleLowPassApprox => {
-                let alpha = omega / (omega + 1.0);
+                let alpha = omega / (omega + T::ONE);
 
-                Ok(Coefficients {
-                    a1: alpha - 1.0,
-                    a2: 0.0,
+                Coefficients {
+                    a1: alpha - T::ONE,
+                    a2: T::ZERO,
                     b0: alpha,
-                    b1: 0.0,
-                    b2: 0.0,
-                })
-            }
-            Type::SinglePoleLowPass => {
-                let omega_t = tan(omega / 2.0);
-                let a0 = 1.0 + omega_t;
-
-                Ok(Coefficients {
-                    a1: (omega_t - 1.0) / a0,
-                    a2: 0.0,
-                    b0: omega_t / a0,
-                    b1: omega_t / a0,
-                    b2: 0.0,
-                })
+                    b1: T::ZERO,
+                    b2: T::ZERO,
+                }
             }
+            Type::SinglePoleLowPass => Coefficients::<T>::single_pole_prewarped(omega),
             Type::LowPass => {
                 // The code for omega_s/c and alpha is currently duplicated due to the single pole
                 // low pass filter not needing it and when creating coefficients are commonly
                 // assumed to be of low computational complexity.
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
 
-                let b0 = (1.0 - omega_c) * 0.5;
-                let b1 = 1.0 - omega_c;
-                let b2 = (1.0 - omega_c) * 0.5;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
+                let b0 = (T::ONE - omega_c) * T::from_f64(0.5);
+                let b1 = T::ONE - omega_c;
+                let b2 = (T::ONE - omega_c) * T::from_f64(0.5);
+                let a0 = T::ONE + alpha;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha;
 
-                let div = 1.0 / a0;
+                let div = T::ONE / a0;
 
-                Ok(Coefficients {
+                Coefficients {
                     a1: a1 * div,
                     a2: a2 * div,
                     b0: b0 * div,
                     b1: b1 * div,
                     b2: b2 * div,
-                })
+                }
             }
             Type::HighPass => {
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
 
-                let b0 = (1.0 + omega_c) * 0.5;
-                let b1 = -(1.0 + omega_c);
-                let b2 = (1.0 + omega_c) * 0.5;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
+                let b0 = (T::ONE + omega_c) * T::from_f64(0.5);
+                let b1 = -(T::ONE + omega_c);
+                let b2 = (T::ONE + omega_c) * T::from_f64(0.5);
+                let a0 = T::ONE + alpha;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha;
 
-                let div = 1.0 / a0;
+                let div = T::ONE / a0;
 
-                Ok(Coefficients {
+                Coefficients {
                     a1: a1 * div,
                     a2: a2 * div,
                     b0: b0 * div,
                     b1: b1 * div,
                     b2: b2 * div,
-                })
+                }
             }
             Type::Notch => {
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
 
-                let b0 = 1.0;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
+                let b0 = T::ONE;
+                let b1 = -T::TWO * omega_c;
+                let b2 = T::ONE;
+                let a0 = T::ONE + alpha;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha;
 
-                let div = 1.0 / a0;
+                let div = T::ONE / a0;
 
-                Ok(Coefficients {
+                Coefficients {
                     a1: a1 * div,
                     a2: a2 * div,
                     b0: b0 * div,
                     b1: b1 * div,
                     b2: b2 * div,
-                })
+                }
             }
             Type::BandPass => {
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
 
-                let b0 = omega_s / 2.0;
-                let b1 = 0.;
-                let b2 = -(omega_s / 2.0);
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
+                let b0 = omega_s / T::TWO;
+                let b1 = T::ZERO;
+                let b2 = -(omega_s / T::TWO);
+                let a0 = T::ONE + alpha;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha;
 
-                let div = 1.0 / a0;
+                let div = T::ONE / a0;
 
-                Ok(Coefficients {
+                Coefficients {
                     a1: a1 * div,
                     a2: a2 * div,
                     b0: b0 * div,
                     b1: b1 * div,
                     b2: b2 * div,
-                })
+                }
             }
             Type::AllPass => {
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = 1.0 - alpha;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0 + alpha;
-                let a0 = 1.0 + alpha;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha;
-
-                Ok(Coefficients {
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
+
+                let b0 = T::ONE - alpha;
+                let b1 = -T::TWO * omega_c;
+                let b2 = T::ONE + alpha;
+                let a0 = T::ONE + alpha;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha;
+
+                Coefficients {
                     a1: a1 / a0,
                     a2: a2 / a0,
                     b0: b0 / a0,
                     b1: b1 / a0,
                     b2: b2 / a0,
-                })
+                }
             }
             Type::LowShelf(db_gain) => {
-                let a = pow(10.0f64,db_gain / 40.0);
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = a * ((a + 1.0) - (a - 1.0) * omega_c + 2.0 * alpha * sqrt(a));
-                let b1 = 2.0 * a * ((a - 1.0) - (a + 1.0) * omega_c);
-                let b2 = a * ((a + 1.0) - (a - 1.0) * omega_c - 2.0 * alpha * sqrt(a));
-                let a0 = (a + 1.0) + (a - 1.0) * omega_c + 2.0 * alpha * sqrt(a);
-                let a1 = -2.0 * ((a - 1.0) + (a + 1.0) * omega_c);
-                let a2 = (a + 1.0) + (a - 1.0) * omega_c - 2.0 * alpha * sqrt(a);
-
-                Ok(Coefficients {
+                let a = T::pow(T::from_f64(10.0), db_gain / T::from_f64(40.0));
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
+
+                let b0 = a * ((a + T::ONE) - (a - T::ONE) * omega_c + T::TWO * alpha * T::sqrt(a));
+                let b1 = T::TWO * a * ((a - T::ONE) - (a + T::ONE) * omega_c);
+                let b2 = a * ((a + T::ONE) - (a - T::ONE) * omega_c - T::TWO * alpha * T::sqrt(a));
+                let a0 = (a + T::ONE) + (a - T::ONE) * omega_c + T::TWO * alpha * T::sqrt(a);
+                let a1 = -T::TWO * ((a - T::ONE) + (a + T::ONE) * omega_c);
+                let a2 = (a + T::ONE) + (a - T::ONE) * omega_c - T::TWO * alpha * T::sqrt(a);
+
+                Coefficients {
                     a1: a1 / a0,
                     a2: a2 / a0,
                     b0: b0 / a0,
                     b1: b1 / a0,
                     b2: b2 / a0,
-                })
+                }
             }
             Type::HighShelf(db_gain) => {
-                let a = pow(10.0f64,db_gain / 40.0);
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = a * ((a + 1.0) + (a - 1.0) * omega_c + 2.0 * alpha * sqrt(a));
-                let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * omega_c);
-                let b2 = a * ((a + 1.0) + (a - 1.0) * omega_c - 2.0 * alpha * sqrt(a));
-                let a0 = (a + 1.0) - (a - 1.0) * omega_c + 2.0 * alpha * sqrt(a);
-                let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * omega_c);
-                let a2 = (a + 1.0) - (a - 1.0) * omega_c - 2.0 * alpha * sqrt(a);
-
-                Ok(Coefficients {
+                let a = T::pow(T::from_f64(10.0), db_gain / T::from_f64(40.0));
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
+
+                let b0 = a * ((a + T::ONE) + (a - T::ONE) * omega_c + T::TWO * alpha * T::sqrt(a));
+                let b1 = -T::TWO * a * ((a - T::ONE) + (a + T::ONE) * omega_c);
+                let b2 = a * ((a + T::ONE) + (a - T::ONE) * omega_c - T::TWO * alpha * T::sqrt(a));
+                let a0 = (a + T::ONE) - (a - T::ONE) * omega_c + T::TWO * alpha * T::sqrt(a);
+                let a1 = T::TWO * ((a - T::ONE) - (a + T::ONE) * omega_c);
+                let a2 = (a + T::ONE) - (a - T::ONE) * omega_c - T::TWO * alpha * T::sqrt(a);
+
+                Coefficients {
                     a1: a1 / a0,
                     a2: a2 / a0,
                     b0: b0 / a0,
                     b1: b1 / a0,
                     b2: b2 / a0,
-                })
+                }
             }
             Type::PeakingEQ(db_gain) => {
-                let a = pow(10.0f64,db_gain / 40.0);
-                let omega_s = sin(omega);
-                let omega_c = cos(omega);
-                let alpha = omega_s / (2.0 * q_value);
-
-                let b0 = 1.0 + alpha * a;
-                let b1 = -2.0 * omega_c;
-                let b2 = 1.0 - alpha * a;
-                let a0 = 1.0 + alpha / a;
-                let a1 = -2.0 * omega_c;
-                let a2 = 1.0 - alpha / a;
-
-                Ok(Coefficients {
+                let a = T::pow(T::from_f64(10.0), db_gain / T::from_f64(40.0));
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                let alpha = omega_s / (T::TWO * q_value);
+
+                let b0 = T::ONE + alpha * a;
+                let b1 = -T::TWO * omega_c;
+                let b2 = T::ONE - alpha * a;
+                let a0 = T::ONE + alpha / a;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha / a;
+
+                Coefficients {
                     a1: a1 / a0,
                     a2: a2 / a0,
                     b0: b0 / a0,
                     b1: b1 / a0,
                     b2: b2 / a0,
-                })
+                }
+            }
+            Type::ConstantQPeaking(db_gain) => {
+                let a = T::pow(T::from_f64(10.0), db_gain / T::from_f64(40.0));
+                let omega_s = T::sin(omega);
+                let omega_c = T::cos(omega);
+                // Widening alpha by the larger of A and 1 / A keeps the poles of a boost, and the
+                // zeros of a cut, at the Q value
+                let alpha = omega_s / (T::TWO * q_value) * a.max(T::ONE / a);
+
+                let b0 = T::ONE + alpha * a;
+                let b1 = -T::TWO * omega_c;
+                let b2 = T::ONE - alpha * a;
+                let a0 = T::ONE + alpha / a;
+                let a1 = -T::TWO * omega_c;
+                let a2 = T::ONE - alpha / a;
+
+                Coefficients {
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                }
+            }
+            Type::Through => Coefficients::<T>::IDENTITY,
+        }
+    }
+
+    /// Recomputes the coefficients in place, with the same result as `from_params`, e.g. for
+    /// sweeping the cutoff of a filter every block. The coefficients are left unchanged on an
+    /// `Err()`.
+    pub fn update_from_params(
+        &mut self,
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Result<(), Errors> {
+        *self = Coefficients::<T>::from_params(filter, fs, f0, q_value)?;
+
+        Ok(())
+    }
+
+    /// Creates coefficients the same way as `from_params`, but with the damping ratio `zeta`
+    /// instead of a Q value, where `Q = 1 / (2 zeta)`. A negative damping ratio results in an
+    /// `Err()`.
+    pub fn from_params_damping(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        zeta: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        Coefficients::<T>::from_params(filter, fs, f0, damping_to_q(zeta))
+    }
+
+    /// Creates coefficients from a conjugate pole pair at radius `r` and angle `theta` (radians
+    /// per sample), giving `a1 = -2 r cos(theta)` and `a2 = r^2`, with the numerator selected by
+    /// `numerator`. The filter is stable for `r` in `[0, 1)`.
+    pub fn from_pole(r: T, theta: T, numerator: NumeratorKind) -> Coefficients<T> {
+        let a1 = -T::TWO * r * T::cos(theta);
+        let a2 = r * r;
+
+        match numerator {
+            NumeratorKind::Resonator => {
+                let gain = (T::ONE - a2) * T::from_f64(0.5);
+
+                Coefficients {
+                    a1,
+                    a2,
+                    b0: gain,
+                    b1: T::ZERO,
+                    b2: -gain,
+                }
+            }
+            NumeratorKind::AllPass => Coefficients {
+                a1,
+                a2,
+                b0: a2,
+                b1: a1,
+                b2: T::ONE,
+            },
+        }
+    }
+
+    /// Creates coefficients the same way as `from_params`, but also rejects Q values smaller than
+    /// `Q_MIN_F64` (including NaN) for the filter types which use Q, i.e. all but the single pole
+    /// low pass filters. A Q of 0 would otherwise divide by zero and give NaN coefficients.
+    pub fn try_from_params(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+    ) -> Result<Coefficients<T>, Errors> {
+        let uses_q = !matches!(
+            filter,
+            Type::SinglePoleLowPassApprox | Type::SinglePoleLowPass | Type::Through
+        );
+
+        if uses_q && (q_value.is_nan() || (T::ZERO..T::from_f64(Q_MIN_F64)).contains(&q_value)) {
+            return Err(Errors::QTooSmall);
+        }
+
+        Coefficients::<T>::from_params(filter, fs, f0, q_value)
+    }
+
+    /// Creates coefficients from an analog (s-domain) transfer function using the bilinear
+    /// transform `s = 2 fs (1 - z^-1) / (1 + z^-1)`, without prewarping. The analog coefficients
+    /// are given in descending powers of `s`, i.e. `H(s) = (b[0] s^2 + b[1] s + b[2]) / (a[0] s^2
+    /// + a[1] s + a[2])`.
+    pub fn from_analog(b: [T; 3], a: [T; 3], fs: Hertz<T>) -> Coefficients<T> {
+        let k = T::TWO * fs.hz();
+        let k2 = k * k;
+
+        let a0 = a[0] * k2 + a[1] * k + a[2];
+        let a1 = T::TWO * (a[2] - a[0] * k2);
+        let a2 = a[0] * k2 - a[1] * k + a[2];
+        let b0 = b[0] * k2 + b[1] * k + b[2];
+        let b1 = T::TWO * (b[2] - b[0] * k2);
+        let b2 = b[0] * k2 - b[1] * k + b[2];
+
+        Coefficients {
+            a1: a1 / a0,
+            a2: a2 / a0,
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+        }
+    }
+
+    /// Creates a high shelf with a shelf slope of `S = 1`, the steepest slope for which the
+    /// magnitude response stays monotonic between DC and Nyquist, i.e. without any overshoot
+    /// beyond `db_gain`. This corresponds to a Q of `1 / sqrt(2)`, and makes for a clean tone
+    /// control.
+    pub fn high_shelf_maxflat(
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        db_gain: impl Into<Decibels<T>>,
+    ) -> Result<Coefficients<T>, Errors> {
+        let db_gain = db_gain.into().db();
+
+        // alpha = sin(w0) / 2 * sqrt((A + 1 / A) * (1 / S - 1) + 2), which for S = 1 is
+        // sin(w0) / sqrt(2) and equals alpha = sin(w0) / (2 Q) with Q = 1 / sqrt(2)
+        Coefficients::<T>::from_params(
+            Type::HighShelf(db_gain),
+            fs,
+            f0,
+            T::from_f64(Q_BUTTERWORTH_F64),
+        )
+    }
+
+    /// Creates a band pass whose magnitude at the center frequency `f0` is `peak_db`, whatever the
+    /// value of `q`. The peak gain of `Type::BandPass` equals `q`, so the numerator is rescaled by
+    /// the ratio of the requested gain to `q`.
+    pub fn band_pass_peak_db(
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q: T,
+        peak_db: impl Into<Decibels<T>>,
+    ) -> Result<Coefficients<T>, Errors> {
+        let peak_db = peak_db.into().db();
+        let coeffs = Coefficients::<T>::from_params(Type::BandPass, fs, f0, q)?;
+        let scale = T::pow(T::from_f64(10.0), peak_db / T::from_f64(20.0)) / q;
+
+        Ok(Coefficients {
+            b0: coeffs.b0 * scale,
+            b1: coeffs.b1 * scale,
+            b2: coeffs.b2 * scale,
+            ..coeffs
+        })
+    }
+
+    /// Creates a low pass and high pass pair split at `f0` which are power complementary, i.e.
+    /// `|H_lp|^2 + |H_hp|^2 = 1` at every frequency. Both are second order Butterworth filters,
+    /// whose bilinear transforms share the same frequency warping, such that the sum of the squared
+    /// magnitudes stays exact after the transform.
+    pub fn power_complementary_pair(
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+    ) -> Result<(Coefficients<T>, Coefficients<T>), Errors> {
+        let low =
+            Coefficients::<T>::from_params(Type::LowPass, fs, f0, T::from_f64(Q_BUTTERWORTH_F64))?;
+        let high =
+            Coefficients::<T>::from_params(Type::HighPass, fs, f0, T::from_f64(Q_BUTTERWORTH_F64))?;
+
+        Ok((low, high))
+    }
+
+    /// Creates a single pole low pass whose -3 dB point is exactly at `f0`, for any `f0` below
+    /// Nyquist, i.e. matched to the -3 dB point of the analog prototype rather than a matched-z
+    /// design. The analog prototype is prewarped before the bilinear transform, which is the
+    /// design used by `Type::SinglePoleLowPass`, unlike `Type::SinglePoleLowPassApprox` whose
+    /// cutoff drifts downwards as `f0` approaches Nyquist. Results in an
+    /// `Err(Errors::OutsideNyquist)` if `f0` is above Nyquist.
+    pub fn single_pole_lowpass_matched(
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+    ) -> Result<Coefficients<T>, Errors> {
+        if T::TWO * f0.hz() > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        Ok(Coefficients::<T>::single_pole_prewarped(
+            T::TWO * T::PI * f0.hz() / fs.hz(),
+        ))
+    }
+
+    /// The bilinear transform of `1 / (1 + s / omega_a)`, with the analog cutoff `omega_a`
+    /// prewarped so that the digital -3 dB point is at `omega` (radians per sample)
+    fn single_pole_prewarped(omega: T) -> Coefficients<T> {
+        let omega_t = T::tan(omega / T::TWO);
+        let a0 = T::ONE + omega_t;
+
+        Coefficients {
+            a1: (omega_t - T::ONE) / a0,
+            a2: T::ZERO,
+            b0: omega_t / a0,
+            b1: omega_t / a0,
+            b2: T::ZERO,
+        }
+    }
+
+    /// Creates a peaking filter centered at `f0` with the given `q`, whose gain at `f_measure` is
+    /// `gain_at_measure_db`, e.g. to match a bump measured off its center. The center gain is
+    /// solved for by bisection, using that the gain at `f_measure` grows monotonically with the
+    /// center gain. `f_measure` must be smaller than half the sampling frequency. Results in an
+    /// `Err(Errors::GainUnreachable)` if no center gain up to 240 dB reaches the gain, e.g. at
+    /// Nyquist, where the gain of a peaking filter is always 0 dB, or far off the center of a
+    /// narrow band.
+    pub fn peaking_gain_at(
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q: T,
+        f_measure: Hertz<T>,
+        gain_at_measure_db: impl Into<Decibels<T>>,
+    ) -> Result<Coefficients<T>, Errors> {
+        let gain_at_measure_db = gain_at_measure_db.into().db();
+        if T::TWO * f_measure.hz() > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        let omega = T::TWO * T::PI * f_measure.hz() / fs.hz();
+        let gain_at_measure = |db_gain: T| -> Result<T, Errors> {
+            let coeffs = Coefficients::<T>::from_params(Type::PeakingEQ(db_gain), fs, f0, q)?;
+            let (re, im) = coeffs.response_at(omega);
+            Ok(T::from_f64(10.0) * T::log10(re * re + im * im))
+        };
+
+        // The center gain is at least the gain at any other frequency, so the solution lies
+        // between the requested gain and a large enough center gain of the same sign. Beyond a
+        // center gain of 240 dB the poles are so close to the unit circle that the designs
+        // stop being meaningful in double precision.
+        let max_db = T::from_f64(240.0);
+        let mut bound = gain_at_measure_db;
+        while T::fabs(gain_at_measure(bound)?) < T::fabs(gain_at_measure_db)
+            && T::fabs(bound) < max_db
+        {
+            bound = (bound * T::TWO).clamp(-max_db, max_db);
+        }
+        let reached = gain_at_measure(bound)?;
+        if reached.is_nan() || T::fabs(reached) < T::fabs(gain_at_measure_db) {
+            return Err(Errors::GainUnreachable);
+        }
+
+        let (mut low, mut high) = if bound < gain_at_measure_db {
+            (bound, gain_at_measure_db)
+        } else {
+            (gain_at_measure_db, bound)
+        };
+        for _ in 0..64 {
+            let mid = (low + high) / T::TWO;
+            if gain_at_measure(mid)? < gain_at_measure_db {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Coefficients::<T>::from_params(Type::PeakingEQ((low + high) / T::TWO), fs, f0, q)
+    }
+
+    /// Creates coefficients from raw values, e.g. precomputed by another tool, where `a0` is
+    /// assumed to be already normalized to 1. Results in an `Err(Errors::InvalidCoefficients)` if
+    /// any value is not finite and in an `Err(Errors::Unstable)` if the poles are not strictly
+    /// inside the unit circle. Use a struct literal to skip the validation.
+    pub fn new(b0: T, b1: T, b2: T, a1: T, a2: T) -> Result<Coefficients<T>, Errors> {
+        let coeffs = Coefficients { a1, a2, b0, b1, b2 };
+
+        if ![a1, a2, b0, b1, b2].iter().all(|c| c.is_finite()) {
+            return Err(Errors::InvalidCoefficients);
+        }
+
+        if !coeffs.is_stable() {
+            return Err(Errors::Unstable);
+        }
+
+        Ok(coeffs)
+    }
+
+    /// Creates coefficients from the numerator `b` and denominator `a` in the convention of
+    /// `scipy.signal`, i.e. in ascending powers of `z^-1`, normalizing by `a[0]`. Both may have up
+    /// to three entries and missing entries are zero, longer inputs are accepted only if the extra
+    /// entries are zero. Results in an `Err(Errors::OrderTooHigh)` for higher orders and in an
+    /// `Err(Errors::InvalidCoefficients)` if `a` is empty or `a[0]` is zero.
+    pub fn from_ba(b: &[T], a: &[T]) -> Result<Coefficients<T>, Errors> {
+        if b.iter()
+            .skip(3)
+            .chain(a.iter().skip(3))
+            .any(|&x| x != T::ZERO)
+        {
+            return Err(Errors::OrderTooHigh);
+        }
+
+        let a0 = match a.first() {
+            Some(&a0) if a0 != T::ZERO => a0,
+            _ => return Err(Errors::InvalidCoefficients),
+        };
+        let at = |p: &[T], i: usize| p.get(i).map_or(T::ZERO, |&x| x / a0);
+
+        Ok(Coefficients {
+            a1: at(a, 1),
+            a2: at(a, 2),
+            b0: at(b, 0),
+            b1: at(b, 1),
+            b2: at(b, 2),
+        })
+    }
+
+    /// Creates the two sections of a 4th order Linkwitz-Riley low pass at `f0`, i.e. two
+    /// cascaded second order Butterworth low passes, e.g. for `Cascade::new`. Together with
+    /// `linkwitz_riley_hp` at the same `f0` the outputs sum to an all pass, both being -6 dB and in
+    /// phase at the crossover frequency.
+    pub fn linkwitz_riley_lp(fs: Hertz<T>, f0: Hertz<T>) -> Result<[Coefficients<T>; 2], Errors> {
+        let section =
+            Coefficients::<T>::from_params(Type::LowPass, fs, f0, T::from_f64(Q_BUTTERWORTH_F64))?;
+
+        Ok([section; 2])
+    }
+
+    /// Creates the two sections of a 4th order Linkwitz-Riley high pass at `f0`, the counterpart
+    /// of `linkwitz_riley_lp`
+    pub fn linkwitz_riley_hp(fs: Hertz<T>, f0: Hertz<T>) -> Result<[Coefficients<T>; 2], Errors> {
+        let section =
+            Coefficients::<T>::from_params(Type::HighPass, fs, f0, T::from_f64(Q_BUTTERWORTH_F64))?;
+
+        Ok([section; 2])
+    }
+
+    /// Creates the sections of an `order`-th order Butterworth low pass at `f0`, e.g. for a
+    /// cascade. The second order sections have the pole Qs `1 / (2 cos(theta_k))` of the
+    /// Butterworth pole angles `theta_k`, sorted by ascending Q, followed by a single pole section
+    /// for odd orders. An order of 0 results in no sections.
+    #[cfg(feature = "alloc")]
+    pub fn butterworth_lowpass(
+        order: usize,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+    ) -> Result<Vec<Coefficients<T>>, Errors> {
+        let n = T::from_usize(order);
+        let mut sections = (0..order / 2)
+            .map(|k| {
+                // Pole angles from the negative real axis, (2k + 1) pi / 2n for even orders and
+                // (k + 1) pi / n for odd orders
+                let theta = T::PI * T::from_usize(2 * k + 1 + order % 2) / (T::TWO * n);
+                let q = T::ONE / (T::TWO * T::cos(theta));
+                Coefficients::<T>::from_params(Type::LowPass, fs, f0, q)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if order % 2 == 1 {
+            sections.push(Coefficients::<T>::from_params(
+                Type::SinglePoleLowPass,
+                fs,
+                f0,
+                T::ZERO,
+            )?);
+        }
+
+        Ok(sections)
+    }
+
+    /// Creates coefficients the same way as `from_params`, with the bandwidth given as a Q value,
+    /// a bandwidth in octaves or a shelf slope, which are converted to the equivalent Q with the
+    /// cookbook formulas for `alpha`. For types other than shelves, the shelf slope is converted
+    /// as for a shelf with a gain of 0 dB.
+    pub fn from_params_with_bandwidth(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        bandwidth: BandwidthParam<T>,
+    ) -> Result<Coefficients<T>, Errors> {
+        let q_value = match bandwidth {
+            BandwidthParam::Q(q_value) => q_value,
+            BandwidthParam::BandwidthOctaves(octaves) => {
+                // alpha = sin(w0) sinh(ln(2) / 2 BW w0 / sin(w0)) and alpha = sin(w0) / 2Q
+                let omega = T::TWO * T::PI * f0.hz() / fs.hz();
+                let warp = if omega > T::ZERO {
+                    omega / T::sin(omega)
+                } else {
+                    T::ONE
+                };
+                T::ONE / (T::TWO * T::sinh(T::LN_2 / T::TWO * octaves * warp))
+            }
+            BandwidthParam::ShelfSlope(slope) => {
+                // alpha = sin(w0) / 2 sqrt((A + 1 / A) (1 / S - 1) + 2) and alpha = sin(w0) / 2Q
+                let a = match filter {
+                    Type::LowShelf(db_gain) | Type::HighShelf(db_gain) => {
+                        T::pow(T::from_f64(10.0), db_gain / T::from_f64(40.0))
+                    }
+                    _ => T::ONE,
+                };
+                T::ONE / T::sqrt((a + T::ONE / a) * (T::ONE / slope - T::ONE) + T::TWO)
+            }
+        };
+
+        Coefficients::<T>::from_params(filter, fs, f0, q_value)
+    }
+
+    /// Creates a second order Bessel low pass, with the Q of `Q_BESSEL_F64`. The Bessel alignment
+    /// has a maximally flat group delay, so pulses pass with minimal overshoot and ringing. The
+    /// cutoff `f0` is the cookbook corner frequency, the -3 dB point lies at about `0.79 f0`. See
+    /// `bessel_lowpass_sections` for higher orders.
+    pub fn bessel_lowpass(fs: Hertz<T>, f0: Hertz<T>) -> Result<Coefficients<T>, Errors> {
+        Coefficients::<T>::from_params(Type::LowPass, fs, f0, T::from_f64(Q_BESSEL_F64))
+    }
+
+    /// Creates the sections of an `order`-th order Bessel low pass with its -3 dB point at `f0`,
+    /// see `Cascade::bessel_lowpass` for a cascade of them. The Bessel alignment has a maximally
+    /// flat group delay, so transients pass with minimal overshoot and ringing. The tabulated
+    /// analog sections are bilinear transformed with the cutoff prewarped, and hold the second
+    /// order sections sorted by ascending Q, followed by a single pole section for odd orders. An
+    /// order of 0 results in no sections and orders above 8 in an `Err(Errors::OrderTooHigh)`.
+    #[cfg(feature = "alloc")]
+    pub fn bessel_lowpass_sections(
+        order: usize,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+    ) -> Result<Vec<Coefficients<T>>, Errors> {
+        if T::TWO * f0.hz() > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+        if order == 0 {
+            return Ok(Vec::new());
+        }
+        let (sections, single_pole) =
+            *BESSEL_SECTIONS.get(order - 1).ok_or(Errors::OrderTooHigh)?;
+
+        let mut coefficients = sections
+            .iter()
+            .map(|&(ratio, q)| {
+                let f = prewarped_frequency(fs, f0, T::from_f64(ratio))?;
+                Coefficients::<T>::from_params(Type::LowPass, fs, f, T::from_f64(q))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(ratio) = single_pole {
+            let f = prewarped_frequency(fs, f0, T::from_f64(ratio))?;
+            coefficients.push(Coefficients::<T>::from_params(
+                Type::SinglePoleLowPass,
+                fs,
+                f,
+                T::ZERO,
+            )?);
+        }
+
+        Ok(coefficients)
+    }
+
+    /// Creates the sections of an `order`-th order Chebyshev type I low pass, see
+    /// `Cascade::chebyshev1_lowpass` for a cascade of them. The response ripples by `ripple_db` in
+    /// the passband, in exchange for a steeper roll off than a Butterworth low pass of the same
+    /// order, and is `ripple_db` below its passband maximum at `f0`. The poles of the analog
+    /// prototype lie on an ellipse determined by the ripple, and its sections are bilinear
+    /// transformed with the cutoff prewarped. The second order sections are sorted by ascending
+    /// Q, followed by a single pole section for odd orders.
+    ///
+    /// As the ripple approaches 0 dB the response approaches a Butterworth low pass, with the
+    /// cutoff moving towards Nyquist, so a ripple of 0 dB or less results in the Butterworth low
+    /// pass of `butterworth_lowpass` instead. An order of 0 results in no sections.
+    #[cfg(feature = "alloc")]
+    pub fn chebyshev1_lowpass(
+        order: usize,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        ripple_db: T,
+    ) -> Result<Vec<Coefficients<T>>, Errors> {
+        if ripple_db <= T::ZERO {
+            return Coefficients::<T>::butterworth_lowpass(order, fs, f0);
+        }
+        if T::TWO * f0.hz() > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+        if order == 0 {
+            return Ok(Vec::new());
+        }
+
+        let n = T::from_usize(order);
+        let epsilon = T::sqrt(T::pow(T::from_f64(10.0), ripple_db / T::from_f64(10.0)) - T::ONE);
+
+        // The semi-axes of the ellipse are sinh(mu) and cosh(mu), with mu = asinh(1 / epsilon) / n
+        let inv_epsilon = T::ONE / epsilon;
+        let mu = T::log(inv_epsilon + T::sqrt(inv_epsilon * inv_epsilon + T::ONE)) / n;
+        let sinh_mu = T::sinh(mu);
+        let cosh_mu = T::sqrt(sinh_mu * sinh_mu + T::ONE);
+
+        // The pole angles of the Butterworth low pass, in descending order for ascending Qs
+        let mut sections = (0..order / 2)
+            .rev()
+            .map(|k| {
+                let theta = T::PI * T::from_usize(2 * k + 1) / (T::TWO * n);
+                let sigma = sinh_mu * T::sin(theta);
+                let ratio = T::hypot(sigma, cosh_mu * T::cos(theta));
+                let f = prewarped_frequency(fs, f0, ratio)?;
+                Coefficients::<T>::from_params(Type::LowPass, fs, f, ratio / (T::TWO * sigma))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if order % 2 == 1 {
+            let f = prewarped_frequency(fs, f0, sinh_mu)?;
+            sections.push(Coefficients::<T>::from_params(
+                Type::SinglePoleLowPass,
+                fs,
+                f,
+                T::ZERO,
+            )?);
+        } else {
+            // The DC gain of even orders is at the bottom of the ripple, as the response at f0
+            let gain = T::ONE / T::sqrt(T::ONE + epsilon * epsilon);
+            sections[0].b0 *= gain;
+            sections[0].b1 *= gain;
+            sections[0].b2 *= gain;
+        }
+
+        Ok(sections)
+    }
+
+    /// Creates the sections of an `order`-th order Chebyshev type I high pass, the counterpart of
+    /// `chebyshev1_lowpass`, see `Cascade::chebyshev1_highpass` for a cascade of them. The
+    /// response ripples by `ripple_db` in the passband above `f0`, and is `ripple_db` below its
+    /// passband maximum at `f0`. This is the low pass at `fs / 2 - f0` with `-z` substituted for
+    /// `z`, mirroring its response around `fs / 4`, which for bilinear transformed designs is
+    /// exactly the analog low pass to high pass transform. The sections are ordered as for the
+    /// low pass, and a ripple of 0 dB or less results in a Butterworth high pass.
+    #[cfg(feature = "alloc")]
+    pub fn chebyshev1_highpass(
+        order: usize,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        ripple_db: T,
+    ) -> Result<Vec<Coefficients<T>>, Errors> {
+        if T::TWO * f0.hz() > fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        let mirrored = Hertz::<T>::from_hz(fs.hz() / T::TWO - f0.hz())?;
+        let mut sections = Coefficients::<T>::chebyshev1_lowpass(order, fs, mirrored, ripple_db)?;
+        for section in sections.iter_mut() {
+            section.b1 = -section.b1;
+            section.a1 = -section.a1;
+        }
+
+        Ok(sections)
+    }
+
+    /// Creates a broad peaking boost of `amount_db` centered at 4 kHz with a Q of 0.9, i.e. a
+    /// bandwidth of about 1.5 octaves, a common preset for bringing vocals forward. Results in an
+    /// `Err()` if `fs` is too low for the center frequency.
+    pub fn presence_boost(
+        fs: Hertz<T>,
+        amount_db: impl Into<Decibels<T>>,
+    ) -> Result<Coefficients<T>, Errors> {
+        let amount_db = amount_db.into().db();
+        let f0 = Hertz::<T>::from_hz(T::from_f64(4_000.0))?;
+
+        Coefficients::<T>::from_params(Type::PeakingEQ(amount_db), fs, f0, T::from_f64(0.9))
+    }
+
+    /// Fits peaking filters to the difference `target - source` of two measured magnitude
+    /// curves, given as `(frequency, dB)` pairs in ascending frequency, e.g. for transferring the
+    /// tone of one recording onto another. The target is interpolated at the source frequencies,
+    /// which should cover the band of interest densely. Bands are placed greedily at the largest
+    /// remaining deviation, with the bandwidth from its half gain points, until `max_bands` are
+    /// used or the deviation is below 0.1 dB. Points at or above Nyquist are ignored.
+    #[cfg(feature = "alloc")]
+    pub fn match_eq(
+        fs: Hertz<T>,
+        source: &[(Hertz<T>, T)],
+        target: &[(Hertz<T>, T)],
+        max_bands: usize,
+    ) -> Vec<Coefficients<T>> {
+        // Linear interpolation of the target over log frequency, held constant past its ends
+        let target_at = |f: T| {
+            let upper = target.iter().position(|&(ft, _)| ft.hz() >= f);
+            match upper {
+                None => target.last().map_or(T::ZERO, |&(_, db)| db),
+                Some(0) => target[0].1,
+                Some(i) => {
+                    let ((f_lo, db_lo), (f_hi, db_hi)) = (target[i - 1], target[i]);
+                    let t = T::log(f / f_lo.hz()) / T::log(f_hi.hz() / f_lo.hz());
+                    db_lo + (db_hi - db_lo) * t
+                }
+            }
+        };
+
+        let mut residual: Vec<(Hertz<T>, T)> = source
+            .iter()
+            .filter(|&&(f, _)| T::TWO * f.hz() < fs.hz())
+            .map(|&(f, db)| (f, target_at(f.hz()) - db))
+            .collect();
+
+        let mut bands = Vec::new();
+        while bands.len() < max_bands {
+            let peak = residual.iter().enumerate().fold(
+                None,
+                |best: Option<(usize, T)>, (i, &(_, db))| match best {
+                    Some((_, max)) if T::fabs(db) <= max => best,
+                    _ => Some((i, T::fabs(db))),
+                },
+            );
+            let (k, gain_db) = match peak {
+                Some((k, max)) if max >= T::from_f64(0.1) => (k, residual[k].1),
+                _ => break,
+            };
+
+            // The first points on either side which fall below half of the gain
+            let half = |&(_, db): &(Hertz<T>, T)| db * gain_db <= gain_db * gain_db / T::TWO;
+            let lo = residual[..k].iter().rposition(half).unwrap_or(0);
+            let hi = residual[k..]
+                .iter()
+                .position(half)
+                .map_or(residual.len() - 1, |i| k + i);
+
+            let f0 = residual[k].0.hz();
+            let (f_lo, f_hi) = match (lo < k, hi > k) {
+                (true, true) => (residual[lo].0.hz(), residual[hi].0.hz()),
+                (true, false) => (residual[lo].0.hz(), f0 * f0 / residual[lo].0.hz()),
+                (false, true) => (f0 * f0 / residual[hi].0.hz(), residual[hi].0.hz()),
+                (false, false) => (f0 / T::TWO, f0 * T::TWO),
+            };
+            let octaves = T::log(f_hi / f_lo) / T::LN_2;
+
+            let band = match Coefficients::<T>::from_params_with_bandwidth(
+                Type::PeakingEQ(gain_db),
+                fs,
+                residual[k].0,
+                BandwidthParam::BandwidthOctaves(octaves),
+            ) {
+                Ok(band) => band,
+                Err(_) => break,
+            };
+
+            for (f, db) in residual.iter_mut() {
+                *db -= band.magnitude_db(*f, fs);
+            }
+            bands.push(band);
+        }
+
+        bands
+    }
+
+    /// Fits a second order allpass whose phase response corrects the phase response of `target`
+    /// within the frequency band `band` (lower, upper). The fit minimizes the variation of the
+    /// combined group delay over the band, i.e. it makes the combined phase as close to a pure
+    /// delay as a single allpass section allows. The magnitude response of the result is flat.
+    /// Results in an `Err(Errors::NegativeFrequency)` if the band is empty, i.e. its upper edge is
+    /// not above its lower edge, and in an `Err(Errors::OutsideNyquist)` if its lower edge is not
+    /// below Nyquist.
+    pub fn phase_match_allpass(
+        target: &Coefficients<T>,
+        fs: Hertz<T>,
+        band: (Hertz<T>, Hertz<T>),
+    ) -> Result<Coefficients<T>, Errors> {
+        const POINTS: usize = 32;
+        const GRID: usize = 16;
+
+        // The log spaced grid below needs a lower edge above 0 and a band of nonzero width
+        if band.0.hz() <= T::ZERO || band.1 <= band.0 {
+            return Err(Errors::NegativeFrequency);
+        }
+        if T::TWO * band.0.hz() >= fs.hz() {
+            return Err(Errors::OutsideNyquist);
+        }
+
+        // Keep the band strictly below Nyquist so every candidate is a valid design
+        let f_hi = band.1.hz().min(T::from_f64(0.499) * fs.hz());
+        let f_lo = band.0.hz().min(f_hi);
+
+        let mut omegas = [T::ZERO; POINTS];
+        let mut target_delay = [T::ZERO; POINTS];
+        for (i, (omega, delay)) in omegas.iter_mut().zip(target_delay.iter_mut()).enumerate() {
+            let f = f_lo * T::pow(f_hi / f_lo, T::from_usize(i) / T::from_usize(POINTS - 1));
+            *omega = T::TWO * T::PI * f / fs.hz();
+            *delay = target.group_delay_at(*omega);
+        }
+
+        let design = |log_f0: T, log_q: T| {
+            let f0 = Hertz::<T>::from_hz(T::pow(T::from_f64(10.0), log_f0)).unwrap();
+            Coefficients::<T>::from_params(Type::AllPass, fs, f0, T::pow(T::from_f64(10.0), log_q))
+                .unwrap()
+        };
+
+        // Cost is the variance of the combined group delay across the band
+        let cost = |allpass: &Coefficients<T>| {
+            let mut delays = [T::ZERO; POINTS];
+            let mut mean = T::ZERO;
+            for ((delay, &omega), &t) in delays
+                .iter_mut()
+                .zip(omegas.iter())
+                .zip(target_delay.iter())
+            {
+                *delay = t + allpass.group_delay_at(omega);
+                mean += *delay / T::from_usize(POINTS);
+            }
+            delays.iter().map(|&d| (d - mean) * (d - mean)).sum::<T>()
+        };
+
+        // The allpass may be centered anywhere up to Nyquist, not only inside the band
+        let log_f_min = T::log10(f_lo) - T::ONE;
+        let log_f_max = T::log10(T::from_f64(0.499) * fs.hz());
+        let log_q_min = -T::ONE;
+        let log_q_max = T::ONE;
+
+        // Coarse grid search, followed by a shrinking coordinate descent
+        let mut best = (log_f_min, T::ZERO, T::INFINITY);
+        for i in 0..GRID {
+            let log_f0 =
+                log_f_min + (log_f_max - log_f_min) * T::from_usize(i) / T::from_usize(GRID - 1);
+            for j in 0..GRID {
+                let log_q = log_q_min
+                    + (log_q_max - log_q_min) * T::from_usize(j) / T::from_usize(GRID - 1);
+                let c = cost(&design(log_f0, log_q));
+                if c < best.2 {
+                    best = (log_f0, log_q, c);
+                }
+            }
+        }
+
+        let mut step_f = (log_f_max - log_f_min) / T::from_usize(GRID - 1);
+        let mut step_q = (log_q_max - log_q_min) / T::from_usize(GRID - 1);
+        for _ in 0..64 {
+            let mut improved = false;
+            for &(df, dq) in &[
+                (step_f, T::ZERO),
+                (-step_f, T::ZERO),
+                (T::ZERO, step_q),
+                (T::ZERO, -step_q),
+            ] {
+                let log_f0 = (best.0 + df).max(log_f_min).min(log_f_max);
+                let log_q = (best.1 + dq).max(log_q_min).min(log_q_max);
+                let c = cost(&design(log_f0, log_q));
+                if c < best.2 {
+                    best = (log_f0, log_q, c);
+                    improved = true;
+                }
+            }
+            if !improved {
+                step_f *= T::from_f64(0.5);
+                step_q *= T::from_f64(0.5);
+            }
+        }
+
+        Ok(design(best.0, best.1))
+    }
+
+    /// Checks if the coefficients are effectively an identity (pass-through) filter, i.e. `b0` is
+    /// within `tol` of 1 and all other coefficients are within `tol` of 0.
+    pub fn is_identity(&self, tol: T) -> bool {
+        T::fabs(self.b0 - T::ONE) <= tol
+            && T::fabs(self.b1) <= tol
+            && T::fabs(self.b2) <= tol
+            && T::fabs(self.a1) <= tol
+            && T::fabs(self.a2) <= tol
+    }
+
+    /// Checks if the coefficients are a second order allpass, i.e. the numerator is the reversed
+    /// denominator `b0 = g a2`, `b1 = g a1`, `b2 = g` within `tol`, for a nonzero gain `g` that
+    /// scales the flat magnitude response.
+    pub fn is_allpass(&self, tol: T) -> bool {
+        let gain = self.b2;
+
+        gain != T::ZERO
+            && T::fabs(self.b0 - gain * self.a2) <= tol
+            && T::fabs(self.b1 - gain * self.a1) <= tol
+    }
+
+    /// A hashable representation of the coefficients, as the bit patterns of `a1`, `a2`, `b0`,
+    /// `b1` and `b2` in that order. Negative zero is folded into zero and all NaNs map to the same
+    /// key, so that equal coefficients always give equal keys, e.g. for use in a `HashMap`.
+    pub fn canonical_key(&self) -> [u64; 5] {
+        let key = |value: T| {
+            if value == T::ZERO {
+                0
+            } else if value.is_nan() {
+                T::NAN.to_bits_u64()
+            } else {
+                value.to_bits_u64()
+            }
+        };
+
+        [
+            key(self.a1),
+            key(self.a2),
+            key(self.b0),
+            key(self.b1),
+            key(self.b2),
+        ]
+    }
+
+    /// A view of the coefficients as an array in the order `a1`, `a2`, `b0`, `b1`, `b2`, without
+    /// copying, e.g. for vectorized math over a slice of coefficients.
+    pub fn as_array_ref(&self) -> &[T; 5] {
+        // SAFETY: `Coefficients` is `repr(C)` with five fields of type `T`, so it has the size,
+        // alignment and field order of `[T; 5]` without padding
+        unsafe { &*(self as *const Self as *const [T; 5]) }
+    }
+
+    /// Checks if all five coefficients are within `epsilon` of the coefficients of `other`, which
+    /// is more robust than the exact comparison of `==` for coefficients from different design
+    /// paths. Coefficients with a NaN are never equal.
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        T::fabs(self.a1 - other.a1) <= epsilon
+            && T::fabs(self.a2 - other.a2) <= epsilon
+            && T::fabs(self.b0 - other.b0) <= epsilon
+            && T::fabs(self.b1 - other.b1) <= epsilon
+            && T::fabs(self.b2 - other.b2) <= epsilon
+    }
+
+    /// Checks if both poles lie strictly inside the unit circle, i.e. `|a2| < 1` and
+    /// `|a1| < 1 + a2`, e.g. for validating coefficients from an external source. Non-finite
+    /// coefficients are never stable.
+    pub fn is_stable(&self) -> bool {
+        let finite = [self.a1, self.a2, self.b0, self.b1, self.b2]
+            .iter()
+            .all(|c| c.is_finite());
+
+        finite && T::fabs(self.a2) < T::ONE && T::fabs(self.a1) < T::ONE + self.a2
+    }
+
+    /// Checks if both zeros, i.e. the roots of `b0 z^2 + b1 z + b2`, lie strictly inside the unit
+    /// circle, using the same conditions as `is_stable` on the numerator normalized by `b0`. Zeros
+    /// on the unit circle, such as those of the cookbook low pass at `z = -1`, are not minimum
+    /// phase. Coefficients with `b0 = 0` delay the output and are never minimum phase. The poles
+    /// are checked by `is_stable`.
+    pub fn is_minimum_phase(&self) -> bool {
+        if self.b0 == T::ZERO {
+            return false;
+        }
+
+        let b1 = self.b1 / self.b0;
+        let b2 = self.b2 / self.b0;
+
+        b1.is_finite() && b2.is_finite() && T::fabs(b2) < T::ONE && T::fabs(b1) < T::ONE + b2
+    }
+
+    /// The gain at DC, i.e. the transfer function at `z = 1`. The response is real there, so the
+    /// gain is signed, negative for filters inverting the polarity at DC.
+    pub fn gain_at_dc(&self) -> T {
+        (self.b0 + self.b1 + self.b2) / (T::ONE + self.a1 + self.a2)
+    }
+
+    /// The gain at the Nyquist frequency, i.e. the transfer function at `z = -1`. The response is
+    /// real there, so the gain is signed, negative for filters inverting the polarity at Nyquist.
+    pub fn gain_at_nyquist(&self) -> T {
+        (self.b0 - self.b1 + self.b2) / (T::ONE - self.a1 + self.a2)
+    }
+
+    /// Estimates the Q value of the coefficients from the poles of the filter, by inverting the
+    /// bilinear transform used by the cookbook designs. This recovers the design Q exactly for
+    /// the low pass, high pass, band pass, notch and all pass filters. Returns `None` when the Q is
+    /// undefined, such as for single pole filters or unstable coefficients.
+    ///
+    /// No sample rate is needed: the Q only depends on the pole radius and angle, not on the
+    /// frequency in Hertz they correspond to.
+    pub fn estimate_q(&self) -> Option<T> {
+        if self.a2 == T::ZERO || self.a2 <= -T::ONE {
+            return None;
+        }
+
+        // With a0 = 1 + alpha, a1 = -2 cos(w0) and a2 = 1 - alpha before normalization
+        let alpha = (T::ONE - self.a2) / (T::ONE + self.a2);
+        let omega_c = -self.a1 / (T::ONE + self.a2);
+
+        if alpha <= T::ZERO || T::fabs(omega_c) >= T::ONE {
+            return None;
+        }
+
+        Some(T::sqrt(T::ONE - omega_c * omega_c) / (T::TWO * alpha))
+    }
+
+    /// Truncates the impulse response of the filter to `taps` samples, applies it as an FIR filter
+    /// on `signal` and returns the RMS error compared to the output of the IIR filter. This is
+    /// helpful to decide how many taps an FIR equivalent of the filter needs.
+    pub fn fir_approx_error(&self, taps: usize, signal: &[T]) -> T {
+        if signal.is_empty() {
+            return T::ZERO;
+        }
+
+        let mut iir = DirectForm2Transposed::<T>::new(*self);
+        let mut error = T::ZERO;
+
+        for n in 0..signal.len() {
+            // The impulse response is regenerated for each sample to avoid allocating the taps
+            let mut impulse = DirectForm2Transposed::<T>::new(*self);
+            let mut fir = T::ZERO;
+            for k in 0..taps.min(n + 1) {
+                let h = impulse.run(if k == 0 { T::ONE } else { T::ZERO });
+                fir += h * signal[n - k];
+            }
+
+            let diff = iir.run(signal[n]) - fir;
+            error += diff * diff;
+        }
+
+        T::sqrt(error / T::from_usize(signal.len()))
+    }
+
+    /// The frequency of the resonant peak of the magnitude response. The pole angle is used as a
+    /// starting point and the peak is then refined numerically, as the peak of the magnitude
+    /// response differs from the design cutoff for resonant filters. Returns `None` for filters
+    /// with real poles or without an interior magnitude peak, such as a Butterworth low pass.
+    pub fn resonant_frequency(&self, fs: Hertz<T>) -> Option<Hertz<T>> {
+        if self.a1 * self.a1 >= T::from_f64(4.0) * self.a2 {
+            return None;
+        }
+
+        let pi = T::PI;
+        let theta = T::acos((-self.a1 / (T::TWO * T::sqrt(self.a2))).clamp(-T::ONE, T::ONE));
+        let magnitude = |omega: T| {
+            let (re, im) = self.response_at(omega);
+            re * re + im * im
+        };
+
+        // Golden section search for the peak in a bracket around the pole angle
+        let ratio = T::from_f64(0.5) * (T::sqrt(T::from_f64(5.0)) - T::ONE);
+        let (mut lo, mut hi) = (T::from_f64(0.5) * theta, T::from_f64(0.5) * (theta + pi));
+        for _ in 0..64 {
+            let m1 = hi - ratio * (hi - lo);
+            let m2 = lo + ratio * (hi - lo);
+            if magnitude(m1) < magnitude(m2) {
+                lo = m1;
+            } else {
+                hi = m2;
+            }
+        }
+        let peak = T::from_f64(0.5) * (lo + hi);
+
+        let peak_magnitude = magnitude(peak);
+        if peak_magnitude <= magnitude(T::ZERO) || peak_magnitude <= magnitude(pi) {
+            return None;
+        }
+
+        Hertz::<T>::from_hz(peak * fs.hz() / (T::TWO * pi)).ok()
+    }
+
+    /// The actual -3 dB frequency of the magnitude response, relative to the gain at DC or at the
+    /// Nyquist frequency, whichever is larger, e.g. for displaying the true cutoff of a low pass
+    /// or high pass. The cookbook designs prewarp the cutoff, so a Butterworth filter has its
+    /// -3 dB point exactly at `f0`, while other Q values move it away from `f0` by an amount that
+    /// depends on the ratio of `f0` to the sample rate. Returns `None` if the response never
+    /// drops 3 dB below the passband, such as for an all pass.
+    pub fn effective_cutoff(&self, fs: Hertz<T>) -> Option<Hertz<T>> {
+        const STEPS: usize = 1024;
+
+        let pi = T::PI;
+        let half = T::from_f64(0.5);
+        let magnitude = |omega: T| {
+            let (re, im) = self.response_at(omega);
+            T::hypot(re, im)
+        };
+
+        // Search from the passband edge towards the other end of the spectrum
+        let (dc, nyquist) = (T::fabs(self.gain_at_dc()), T::fabs(self.gain_at_nyquist()));
+        let (start, end, passband) = if dc >= nyquist {
+            (T::ZERO, pi, dc)
+        } else {
+            (pi, T::ZERO, nyquist)
+        };
+        let threshold = passband * T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+        if !threshold.is_finite() || threshold <= T::ZERO {
+            return None;
+        }
+
+        let at = |i: usize| start + (end - start) * T::from_usize(i) / T::from_usize(STEPS);
+        let i = (1..=STEPS).find(|&i| magnitude(at(i)) < threshold)?;
+
+        // Bisection between the last step above and the first step below the threshold
+        let (mut above, mut below) = (at(i - 1), at(i));
+        for _ in 0..64 {
+            let mid = half * (above + below);
+            if magnitude(mid) < threshold {
+                below = mid;
+            } else {
+                above = mid;
+            }
+        }
+
+        Hertz::<T>::from_hz(half * (above + below) * fs.hz() / (T::TWO * pi)).ok()
+    }
+
+    /// The complex frequency response, as (real, imaginary), at the normalized angular frequency
+    /// `omega` (radians per sample)
+    pub(crate) fn response_at(&self, omega: T) -> (T, T) {
+        let (s1, c1) = (T::sin(omega), T::cos(omega));
+        let (s2, c2) = (T::sin(T::TWO * omega), T::cos(T::TWO * omega));
+
+        let num_re = self.b0 + self.b1 * c1 + self.b2 * c2;
+        let num_im = -(self.b1 * s1 + self.b2 * s2);
+        let den_re = T::ONE + self.a1 * c1 + self.a2 * c2;
+        let den_im = -(self.a1 * s1 + self.a2 * s2);
+
+        let den = den_re * den_re + den_im * den_im;
+        (
+            (num_re * den_re + num_im * den_im) / den,
+            (num_im * den_re - num_re * den_im) / den,
+        )
+    }
+
+    /// The two poles of the filter, i.e. the roots of `z^2 + a1 z + a2`, as (real, imaginary)
+    pub fn poles(&self) -> [(T, T); 2] {
+        let disc = self.a1 * self.a1 - T::from_f64(4.0) * self.a2;
+
+        if disc < T::ZERO {
+            let im = T::from_f64(0.5) * T::sqrt(-disc);
+            [
+                (-T::from_f64(0.5) * self.a1, im),
+                (-T::from_f64(0.5) * self.a1, -im),
+            ]
+        } else {
+            let root = T::from_f64(0.5) * T::sqrt(disc);
+            [
+                (-T::from_f64(0.5) * self.a1 + root, T::ZERO),
+                (-T::from_f64(0.5) * self.a1 - root, T::ZERO),
+            ]
+        }
+    }
+
+    /// Recovers the analog (s-domain) transfer function by applying the inverse bilinear transform
+    /// `z = (2 fs + s) / (2 fs - s)`, returning `(b, a)` in descending powers of `s` normalized to
+    /// `a[0] = 1`. As the bilinear transform warps the frequency axis, a digital frequency `f`
+    /// corresponds to the analog frequency `2 fs tan(pi f / fs)` rad/s in the result.
+    pub fn to_analog(&self, fs: Hertz<T>) -> ([T; 3], [T; 3]) {
+        let k = T::TWO * fs.hz();
+        let k2 = k * k;
+
+        let a0 = T::ONE - self.a1 + self.a2;
+        let a = [
+            T::ONE,
+            T::TWO * k * (T::ONE - self.a2) / a0,
+            k2 * (T::ONE + self.a1 + self.a2) / a0,
+        ];
+        let b = [
+            (self.b0 - self.b1 + self.b2) / a0,
+            T::TWO * k * (self.b0 - self.b2) / a0,
+            k2 * (self.b0 + self.b1 + self.b2) / a0,
+        ];
+
+        (b, a)
+    }
+
+    /// The percent overshoot of the step response, `(peak - final) / final * 100`, where `final` is
+    /// the settled output. Returns 0 for monotonic step responses and for filters without
+    /// any DC gain. The step response is simulated until the poles have decayed.
+    pub fn step_overshoot(&self) -> T {
+        let dc_gain = self.gain_at_dc();
+        if dc_gain == T::ZERO || !dc_gain.is_finite() {
+            return T::ZERO;
+        }
+
+        let radius = self
+            .poles()
+            .iter()
+            .map(|&(re, im)| T::hypot(re, im))
+            .fold(T::ZERO, T::max);
+        if radius >= T::ONE {
+            return T::ZERO;
+        }
+
+        // Simulate until the slowest pole has decayed by 1e-9, bounded to keep the cost finite
+        let len = if radius > T::ZERO {
+            (T::log(T::from_f64(1e-9)) / T::log(radius))
+                .min(T::from_usize(1 << 20))
+                .to_f64() as usize
+                + 1
+        } else {
+            1
+        };
+
+        let mut biquad = DirectForm2Transposed::<T>::new(*self);
+        let (mut peak, mut last) = (T::ZERO, T::ZERO);
+        for _ in 0..len {
+            last = biquad.run(T::ONE) / dc_gain;
+            peak = peak.max(last);
+        }
+
+        // The settled output is used as the final value, to not count rounding errors in the
+        // DC gain as overshoot
+        (peak / last - T::ONE).max(T::ZERO) * T::from_f64(100.0)
+    }
+
+    /// The energy of the impulse response, i.e. the sum of the squared impulse response over the
+    /// first `len` samples. For a stable filter and a long enough `len` this is the squared L2
+    /// norm of the filter.
+    pub fn impulse_energy(&self, len: usize) -> T {
+        let mut biquad = DirectForm2Transposed::<T>::new(*self);
+
+        (0..len)
+            .map(|n| biquad.run(if n == 0 { T::ONE } else { T::ZERO }))
+            .map(|h| h * h)
+            .sum()
+    }
+
+    /// An FIR filter of `len` taps morphing between `a` at `t = 0` and `b` at `t = 1`, built by
+    /// crossfading the truncated impulse responses of both filters. Unlike interpolating the
+    /// coefficients, the morph is always stable, at the cost of a convolution, e.g. for offline
+    /// rendering.
+    #[cfg(feature = "alloc")]
+    pub fn morph_via_convolution(a: &Self, b: &Self, t: T, len: usize) -> Vec<T> {
+        let mut biquad_a = DirectForm2Transposed::<T>::new(*a);
+        let mut biquad_b = DirectForm2Transposed::<T>::new(*b);
+
+        (0..len)
+            .map(|n| {
+                let impulse = if n == 0 { T::ONE } else { T::ZERO };
+                (T::ONE - t) * biquad_a.run(impulse) + t * biquad_b.run(impulse)
+            })
+            .collect()
+    }
+
+    /// Combines two biquads in series into a single biquad, which is only possible when both the
+    /// product of the numerators and the product of the denominators are at most of second order,
+    /// e.g. a pure gain with any biquad or two first order sections. Returns `None` otherwise.
+    pub fn try_combine(&self, other: &Self) -> Option<Coefficients<T>> {
+        let order = |p: [T; 3]| {
+            if p[2] != T::ZERO {
+                2
+            } else if p[1] != T::ZERO {
+                1
+            } else {
+                0
+            }
+        };
+
+        let (n1, n2) = ([self.b0, self.b1, self.b2], [other.b0, other.b1, other.b2]);
+        let (d1, d2) = ([T::ONE, self.a1, self.a2], [T::ONE, other.a1, other.a2]);
+
+        if order(n1) + order(n2) > 2 || order(d1) + order(d2) > 2 {
+            return None;
+        }
+
+        // Polynomial products, the terms above second order are zero at this point
+        let mul = |p: [T; 3], q: [T; 3]| {
+            [
+                p[0] * q[0],
+                p[0] * q[1] + p[1] * q[0],
+                p[0] * q[2] + p[1] * q[1] + p[2] * q[0],
+            ]
+        };
+        let (n, d) = (mul(n1, n2), mul(d1, d2));
+
+        Some(Coefficients {
+            a1: d[1],
+            a2: d[2],
+            b0: n[0],
+            b1: n[1],
+            b2: n[2],
+        })
+    }
+
+    /// The steady state response to the sinusoid `amplitude * sin(2 pi f t + phase)` of frequency
+    /// `f`, sampled at `fs`, as the output `(amplitude, phase)` with the phase in radians, wrapped
+    /// to `[-pi, pi]`. This is derived from the frequency response, not simulated.
+    pub fn apply_to_sinusoid(&self, fs: Hertz<T>, f: Hertz<T>, amplitude: T, phase: T) -> (T, T) {
+        let omega = T::TWO * T::PI * f.hz() / fs.hz();
+        let (re, im) = self.response_at(omega);
+
+        let phase = phase + T::atan2(im, re);
+        (
+            amplitude * T::hypot(re, im),
+            T::atan2(T::sin(phase), T::cos(phase)),
+        )
+    }
+
+    /// The worst case L2 gain from the input to any node of a Direct Form 2 Transposed biquad,
+    /// i.e. the largest L2 norm among the transfer functions to the output and to both states.
+    /// This bounds the internal signal growth for headroom planning, e.g. in fixed point, where
+    /// `log2` of the gain is the number of extra bits needed. Unstable coefficients result in an
+    /// infinite gain.
+    pub fn max_internal_gain(&self) -> T {
+        if !self.is_stable() {
+            return T::INFINITY;
+        }
+
+        let mut biquad = DirectForm2Transposed::<T>::new(*self);
+        let mut energy = [T::ZERO; 3];
+        for n in 0..(1 << 20) {
+            let y = biquad.run(if n == 0 { T::ONE } else { T::ZERO });
+            let nodes = [y * y, biquad.s1 * biquad.s1, biquad.s2 * biquad.s2];
+
+            for (total, node) in energy.iter_mut().zip(nodes) {
+                *total += node;
+            }
+
+            // Stop once the impulse response has decayed
+            if n > 0
+                && nodes.iter().copied().sum::<T>()
+                    <= T::from_f64(1e-12) * energy.iter().copied().sum::<T>()
+            {
+                break;
+            }
+        }
+
+        T::sqrt(energy.iter().fold(T::ZERO, |max, &e| max.max(e)))
+    }
+
+    /// The phase response in radians at the frequency `f` for the sampling frequency `fs`, wrapped
+    /// to `[-pi, pi]`. Only the angle of the complex response is computed, not its magnitude.
+    pub fn phase_at(&self, fs: Hertz<T>, f: Hertz<T>) -> T {
+        let (re, im) = self.response_at(T::TWO * T::PI * f.hz() / fs.hz());
+
+        T::atan2(im, re)
+    }
+
+    /// Approximately retunes the filter for a sampling frequency multiplied by `ratio`, as if it
+    /// were designed for `ratio * fs`, e.g. to keep the cutoff at the same absolute frequency when
+    /// playback runs `ratio` times as fast. Following `z = e^(s / fs)`, every pole and zero at
+    /// `r e^(j theta)` moves to `r^(1 / ratio) e^(j theta / ratio)`, while real poles and zeros
+    /// keep their sign, so zeros at Nyquist stay there. This matches a redesign only
+    /// approximately, since the bilinear transform warps frequencies differently. The peak
+    /// magnitude is preserved.
+    pub fn with_sample_rate_ratio(&self, ratio: T) -> Coefficients<T> {
+        let scale_root = |x: T| {
+            if x < T::ZERO {
+                -T::pow(-x, T::ONE / ratio)
+            } else {
+                T::pow(x, T::ONE / ratio)
+            }
+        };
+        // Maps the roots of p[0] + p[1] z^-1 + p[2] z^-2
+        let map_roots = |p: [T; 3]| -> [T; 3] {
+            if p[0] != T::ZERO {
+                let (c1, c2) = (p[1] / p[0], p[2] / p[0]);
+                let disc = c1 * c1 - T::from_f64(4.0) * c2;
+                let (c1, c2) = if disc < T::ZERO {
+                    let r = T::sqrt(c2);
+                    let theta = T::acos((-c1 / (T::TWO * r)).clamp(-T::ONE, T::ONE));
+                    let r = T::pow(r, T::ONE / ratio);
+                    (-T::TWO * r * T::cos(theta / ratio), r * r)
+                } else {
+                    let (x1, x2) = (
+                        (-c1 + T::sqrt(disc)) / T::TWO,
+                        (-c1 - T::sqrt(disc)) / T::TWO,
+                    );
+                    let (x1, x2) = (scale_root(x1), scale_root(x2));
+                    (-(x1 + x2), x1 * x2)
+                };
+                [p[0], p[0] * c1, p[0] * c2]
+            } else if p[1] != T::ZERO {
+                [T::ZERO, p[1], -p[1] * scale_root(-p[2] / p[1])]
+            } else {
+                p
             }
+        };
+
+        let [_, a1, a2] = map_roots([T::ONE, self.a1, self.a2]);
+        let [b0, b1, b2] = map_roots([self.b0, self.b1, self.b2]);
+        let retuned = Coefficients { a1, a2, b0, b1, b2 };
+
+        let peak = |coeffs: &Coefficients<T>| {
+            (0..=512)
+                .map(|i| {
+                    let (re, im) =
+                        coeffs.response_at(T::PI * T::from_usize(i) / T::from_f64(512.0));
+                    T::hypot(re, im)
+                })
+                .fold(T::ZERO, T::max)
+        };
+        let gain = peak(self) / peak(&retuned);
+
+        Coefficients {
+            b0: b0 * gain,
+            b1: b1 * gain,
+            b2: b2 * gain,
+            ..retuned
         }
     }
+
+    /// The frequency response `H(e^(j omega))` at the frequency `f` for the sampling frequency
+    /// `fs`, with `omega = 2 pi f / fs`, as its magnitude and its phase in radians. Frequencies
+    /// from DC up to and including Nyquist are supported.
+    pub fn frequency_response(&self, f: Hertz<T>, fs: Hertz<T>) -> (T, T) {
+        let (re, im) = self.response_at(T::TWO * T::PI * f.hz() / fs.hz());
+
+        (T::hypot(re, im), T::atan2(im, re))
+    }
+
+    /// The phase response in radians at the frequency `f` for the sampling frequency `fs`, i.e.
+    /// the phase of `frequency_response`, wrapped to `[-pi, pi]`. Same as `phase_at`, which takes
+    /// the sampling frequency first.
+    pub fn phase_response(&self, f: Hertz<T>, fs: Hertz<T>) -> T {
+        self.phase_at(fs, f)
+    }
+
+    /// The group delay in samples at the frequency `f` for the sampling frequency `fs`, i.e. the
+    /// negative derivative of the phase response with respect to `omega = 2 pi f / fs`, computed
+    /// analytically from the coefficients
+    pub fn group_delay(&self, f: Hertz<T>, fs: Hertz<T>) -> T {
+        self.group_delay_at(T::TWO * T::PI * f.hz() / fs.hz())
+    }
+
+    /// The magnitude response in decibels, `20 log10(|H|)`, at the frequency `f` for the sampling
+    /// frequency `fs`. The result is clamped to at least -300 dB, so exact zeros such as the center
+    /// of a notch give a finite value for plotting.
+    pub fn magnitude_db(&self, f: Hertz<T>, fs: Hertz<T>) -> T {
+        let (magnitude, _) = self.frequency_response(f, fs);
+
+        (T::from_f64(20.0) * T::log10(magnitude)).max(-T::from_f64(300.0))
+    }
+
+    /// Writes the magnitude response in decibels, see `magnitude_db`, at `out.len()` frequencies
+    /// spaced logarithmically from `f_min` to `f_max` into `out`, e.g. one value per pixel of a
+    /// plot without allocating. A single point is at `f_min`, and an empty `out` is left as is.
+    pub fn magnitude_response_into(
+        &self,
+        fs: Hertz<T>,
+        f_min: Hertz<T>,
+        f_max: Hertz<T>,
+        out: &mut [T],
+    ) {
+        let steps = T::from_usize(out.len().max(2) - 1);
+        let ratio = f_max.hz() / f_min.hz();
+
+        for (i, db) in out.iter_mut().enumerate() {
+            let f = f_min.hz() * T::pow(ratio, T::from_usize(i) / steps);
+            *db = self.magnitude_db(Hertz::<T>::from_hz(f).unwrap_or(f_min), fs);
+        }
+    }
+
+    /// The magnitude response in decibels at `points` logarithmically spaced frequencies from
+    /// `f_min` to `f_max`, see `magnitude_response_into`
+    #[cfg(feature = "alloc")]
+    pub fn magnitude_response(
+        &self,
+        fs: Hertz<T>,
+        f_min: Hertz<T>,
+        f_max: Hertz<T>,
+        points: usize,
+    ) -> Vec<T> {
+        let mut out = alloc::vec![T::ZERO; points];
+        self.magnitude_response_into(fs, f_min, f_max, &mut out);
+
+        out
+    }
+
+    /// Group delay, in samples, at the normalized angular frequency `omega` (radians per sample)
+    pub(crate) fn group_delay_at(&self, omega: T) -> T {
+        let (s1, c1) = (T::sin(omega), T::cos(omega));
+        let (s2, c2) = (T::sin(T::TWO * omega), T::cos(T::TWO * omega));
+
+        // For a polynomial P(z^-1) = sum(p_k z^-k) the group delay is Re(sum(k p_k z^-k) / P)
+        let delay = |p0: T, p1: T, p2: T| {
+            let re = p0 + p1 * c1 + p2 * c2;
+            let im = -(p1 * s1 + p2 * s2);
+            let d_re = p1 * c1 + T::TWO * p2 * c2;
+            let d_im = -(p1 * s1 + T::TWO * p2 * s2);
+            (d_re * re + d_im * im) / (re * re + im * im)
+        };
+
+        delay(self.b0, self.b1, self.b2) - delay(T::ONE, self.a1, self.a2)
+    }
 }
@@ -0,0 +1,101 @@
+//! # debug
+//!
+//! A diagnostics wrapper which records the most recent input and output samples of a biquad, so
+//! they can be dumped when a filter misbehaves in the field.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! // Keep the last 64 input/output pairs of a Direct Form 1 biquad
+//! let mut biquad = DebugBiquad::<_, f32, 64>::new(DirectForm1::<f32>::new(coeffs));
+//!
+//! let output = biquad.run(1.0);
+//!
+//! assert_eq!(biquad.history(), &[(1.0, output)]);
+//! ```
+
+use crate::{Biquad, Coefficients};
+
+/// A biquad which records the last `N` `(input, output)` pairs it processed, in a ring buffer so
+/// that recording takes constant time per sample
+#[derive(Copy, Clone, Debug)]
+pub struct DebugBiquad<B, T, const N: usize> {
+    filter: B,
+    history: [(T, T); N],
+    // Index of the oldest pair, once the history is full
+    head: usize,
+    len: usize,
+}
+
+impl<B: Biquad<T>, T: Copy + Default, const N: usize> DebugBiquad<B, T, N> {
+    /// Wraps a biquad with an initially empty history
+    pub fn new(filter: B) -> Self {
+        DebugBiquad {
+            filter,
+            history: [(T::default(), T::default()); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// The recorded `(input, output)` pairs, from the oldest to the most recent, holding fewer
+    /// than `N` pairs until `N` samples have been processed. Once the history has wrapped around
+    /// the ring buffer, the buffer is rotated in place to put the pairs in order, which takes time
+    /// proportional to `N`, so call this when dumping the history rather than per sample.
+    pub fn history(&mut self) -> &[(T, T)] {
+        self.history[..self.len].rotate_left(self.head);
+        self.head = 0;
+
+        &self.history[..self.len]
+    }
+
+    /// Discards the recorded history
+    pub fn clear_history(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Returns the wrapped biquad
+    pub fn into_inner(self) -> B {
+        self.filter
+    }
+}
+
+impl<B: Biquad<T>, T: Copy, const N: usize> Biquad<T> for DebugBiquad<B, T, N> {
+    fn run(&mut self, input: T) -> T {
+        let output = self.filter.run(input);
+
+        if N > 0 {
+            if self.len == N {
+                // Overwrite the oldest pair
+                self.history[self.head] = (input, output);
+                self.head = (self.head + 1) % N;
+            } else {
+                self.history[self.len] = (input, output);
+                self.len += 1;
+            }
+        }
+
+        output
+    }
+
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
+        self.filter.update_coefficients(new_coefficients);
+    }
+
+    fn replace_coefficients(&mut self, new_coefficients: Coefficients<T>) -> Coefficients<T> {
+        self.filter.replace_coefficients(new_coefficients)
+    }
+
+    fn reset_state(&mut self) {
+        self.filter.reset_state();
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.filter.latency_samples()
+    }
+}
@@ -0,0 +1,158 @@
+//! # fixed
+//!
+//! Fixed-point biquads for targets without an FPU. The coefficients are stored as `i32` with the
+//! number of fractional bits, `FRAC`, encoded in the type, so a set of coefficients in one format
+//! can not accidentally be used with a filter expecting another. A format of Q1.30 (`FRAC = 30`)
+//! can represent coefficients in the range `[-2, 2)`, which covers the denominator of all stable
+//! biquads. Formats with fewer fractional bits, such as Q1.15 (`FRAC = 15`), trade precision for
+//! headroom, while Q1.31 (`FRAC = 31`) only represents coefficients in `[-1, 1)`.
+//!
+//! The arithmetic saturates, so an overflow clips at `i32::MIN`/`i32::MAX` instead of wrapping
+//! around, both when converting the coefficients and when running the filter.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! // Convert to Q1.30 and create a fixed-point biquad
+//! let mut biquad = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+//!
+//! let output = biquad.run(1 << 16);
+//! ```
+
+use crate::float::Float;
+use crate::Coefficients;
+use libm::round;
+
+/// Holder of fixed-point biquad coefficients with `FRAC` fractional bits, utilizes normalized form
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FixedCoefficients<const FRAC: u32> {
+    // Denominator coefficients
+    pub a1: i32,
+    pub a2: i32,
+
+    // Nominator coefficients
+    pub b0: i32,
+    pub b1: i32,
+    pub b2: i32,
+}
+
+impl<const FRAC: u32> FixedCoefficients<FRAC> {
+    // Evaluated at compile time for every `FRAC` passed to `to_fixed`
+    const VALID_FRAC: () = assert!(FRAC < 32, "FRAC must be smaller than 32");
+}
+
+impl<T: Float> Coefficients<T> {
+    /// Converts the coefficients to fixed-point with `FRAC` fractional bits, rounding to nearest.
+    /// Coefficients outside of the representable range saturate to `i32::MIN`/`i32::MAX`.
+    /// `FRAC` must be smaller than 32, larger values are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use biquad::*;
+    ///
+    /// let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), 0.7);
+    /// let fixed = coeffs.unwrap().to_fixed::<32>();
+    /// ```
+    pub fn to_fixed<const FRAC: u32>(&self) -> FixedCoefficients<FRAC> {
+        let () = FixedCoefficients::<FRAC>::VALID_FRAC;
+
+        // Scaling by a power of two is exact, so rounding in f64 matches rounding in T
+        let scale = (1u64 << FRAC) as f64;
+        let convert = |c: T| round(c.to_f64() * scale) as i32;
+
+        FixedCoefficients {
+            a1: convert(self.a1),
+            a2: convert(self.a2),
+            b0: convert(self.b0),
+            b1: convert(self.b1),
+            b2: convert(self.b2),
+        }
+    }
+}
+
+/// Internal states and coefficients of the fixed-point Direct Form 1 form
+#[derive(Copy, Clone, Debug)]
+pub struct DirectForm1Fixed<const FRAC: u32> {
+    y1: i32,
+    y2: i32,
+    x1: i32,
+    x2: i32,
+    coeffs: FixedCoefficients<FRAC>,
+}
+
+impl<const FRAC: u32> DirectForm1Fixed<FRAC> {
+    /// Creates a fixed-point Direct Form 1 biquad from a set of fixed-point filter coefficients
+    pub fn new(coefficients: FixedCoefficients<FRAC>) -> Self {
+        DirectForm1Fixed {
+            y1: 0,
+            y2: 0,
+            x1: 0,
+            x2: 0,
+            coeffs: coefficients,
+        }
+    }
+
+    /// A single iteration of the biquad, applying the filtering on the input. The products are
+    /// accumulated in 64 bits with saturating arithmetic, and the result is rounded back to the
+    /// scale of the input, saturating at `i32::MIN`/`i32::MAX`. An overflow therefore clips the
+    /// output instead of wrapping around.
+    pub fn run(&mut self, input: i32) -> i32 {
+        let acc = (self.coeffs.b0 as i64 * input as i64)
+            .saturating_add(self.coeffs.b1 as i64 * self.x1 as i64)
+            .saturating_add(self.coeffs.b2 as i64 * self.x2 as i64)
+            .saturating_sub(self.coeffs.a1 as i64 * self.y1 as i64)
+            .saturating_sub(self.coeffs.a2 as i64 * self.y2 as i64);
+
+        let rounding = (1i64 << FRAC) >> 1;
+        let out =
+            (acc.saturating_add(rounding) >> FRAC).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+
+        self.x2 = self.x1;
+        self.x1 = input;
+        self.y2 = self.y1;
+        self.y1 = out;
+
+        out
+    }
+
+    /// A single iteration of the biquad for full scale 32 bit PCM, i.e. input and output are
+    /// Q1.31 samples in `[-1, 1)`. This is `run`, as the sample scale is independent of the
+    /// coefficient format `FRAC`, and the output saturates at `i32::MIN`/`i32::MAX` the same way.
+    pub fn run_i32(&mut self, sample: i32) -> i32 {
+        self.run(sample)
+    }
+
+    /// A single iteration of the biquad for full scale 16 bit PCM, i.e. input and output are
+    /// Q1.15 samples in `[-1, 1)`. The samples are filtered with the 32 bit precision of
+    /// `run_i32`, and only the output is rounded back to 16 bits, saturating at
+    /// `i16::MIN`/`i16::MAX`.
+    pub fn run_i16(&mut self, sample: i16) -> i16 {
+        let out = self.run_i32((sample as i32) << 16) as i64;
+
+        ((out + (1 << 15)) >> 16).clamp(i16::MIN as i64, i16::MAX as i64) as i16
+    }
+
+    /// Updating of coefficients
+    pub fn update_coefficients(&mut self, new_coefficients: FixedCoefficients<FRAC>) {
+        self.coeffs = new_coefficients;
+    }
+
+    /// Updating coefficients and returning the old ones
+    pub fn replace_coefficients(
+        &mut self,
+        new_coefficients: FixedCoefficients<FRAC>,
+    ) -> FixedCoefficients<FRAC> {
+        core::mem::replace(&mut self.coeffs, new_coefficients)
+    }
+
+    /// Set the internal state of the biquad to 0
+    pub fn reset_state(&mut self) {
+        self.x1 = 0;
+        self.x2 = 0;
+        self.y1 = 0;
+        self.y2 = 0;
+    }
+}
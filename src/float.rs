@@ -0,0 +1,332 @@
+//! # float
+//!
+//! The floating point types the generic parts of the crate are implemented for. `Float` bundles
+//! the arithmetic, constants and `libm` functions used by the coefficient designs, so that
+//! `Coefficients<T>` only needs to be implemented once for all supported sample types.
+//!
+//! The trait is sealed and implemented for `f32` and `f64`.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! fn design<T: Float>(fs: Hertz<T>, f0: Hertz<T>) -> Result<Coefficients<T>, Errors> {
+//!     Coefficients::<T>::from_params(Type::LowPass, fs, f0, T::from_f64(Q_BUTTERWORTH_F64))
+//! }
+//!
+//! let coeffs = design::<f32>(44.1.khz(), 1.khz());
+//! ```
+
+use core::convert::TryInto;
+use core::fmt::Debug;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use libm::{
+    acos, acosf, atan, atan2, atan2f, atanf, cos, cosf, fabs, fabsf, hypot, hypotf, log, log10,
+    log10f, logf, pow, powf, sin, sinf, sinh, sinhf, sqrt, sqrtf, tan, tanf, tanh, tanhf,
+};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A floating point type the coefficients can be designed in
+pub trait Float:
+    sealed::Sealed
+    + Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + From<u8>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const PI: Self;
+    const LN_2: Self;
+    const INFINITY: Self;
+    const NAN: Self;
+    const EPSILON: Self;
+
+    /// The size in bytes of the little endian encoding, see `write_le`
+    const SIZE: usize;
+
+    /// Converts from `f64`, rounding to the nearest value
+    fn from_f64(x: f64) -> Self;
+
+    /// Converts to `f64`, which is exact for all implementors
+    fn to_f64(self) -> f64;
+
+    /// Converts from `usize`, rounding to the nearest value
+    fn from_usize(n: usize) -> Self;
+
+    /// The bit pattern of the value, zero extended to 64 bits
+    fn to_bits_u64(self) -> u64;
+
+    /// Writes the little endian bytes of the value to `buf`. Panics if `buf` is not `SIZE` bytes
+    /// long.
+    fn write_le(self, buf: &mut [u8]);
+
+    /// Reads a value from the little endian bytes in `buf`. Panics if `buf` is not `SIZE` bytes
+    /// long.
+    fn read_le(buf: &[u8]) -> Self;
+
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
+    fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn clamp(self, min: Self, max: Self) -> Self;
+
+    fn fabs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn pow(self, exponent: Self) -> Self;
+    fn log(self) -> Self;
+    fn log10(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn tanh(self) -> Self;
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const TWO: Self = 2.0;
+    const PI: Self = core::f32::consts::PI;
+    const LN_2: Self = core::f32::consts::LN_2;
+    const INFINITY: Self = f32::INFINITY;
+    const NAN: Self = f32::NAN;
+    const EPSILON: Self = f32::EPSILON;
+    const SIZE: usize = 4;
+
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_usize(n: usize) -> Self {
+        n as f32
+    }
+
+    fn to_bits_u64(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    fn write_le(self, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_le(buf: &[u8]) -> Self {
+        f32::from_le_bytes(buf.try_into().unwrap())
+    }
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
+
+    fn max(self, other: Self) -> Self {
+        self.max(other)
+    }
+
+    fn min(self, other: Self) -> Self {
+        self.min(other)
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        self.clamp(min, max)
+    }
+
+    fn fabs(self) -> Self {
+        fabsf(self)
+    }
+
+    fn sqrt(self) -> Self {
+        sqrtf(self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        hypotf(self, other)
+    }
+
+    fn pow(self, exponent: Self) -> Self {
+        powf(self, exponent)
+    }
+
+    fn log(self) -> Self {
+        logf(self)
+    }
+
+    fn log10(self) -> Self {
+        log10f(self)
+    }
+
+    fn sin(self) -> Self {
+        sinf(self)
+    }
+
+    fn cos(self) -> Self {
+        cosf(self)
+    }
+
+    fn tan(self) -> Self {
+        tanf(self)
+    }
+
+    fn acos(self) -> Self {
+        acosf(self)
+    }
+
+    fn atan(self) -> Self {
+        atanf(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        atan2f(self, other)
+    }
+
+    fn sinh(self) -> Self {
+        sinhf(self)
+    }
+
+    fn tanh(self) -> Self {
+        tanhf(self)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const TWO: Self = 2.0;
+    const PI: Self = core::f64::consts::PI;
+    const LN_2: Self = core::f64::consts::LN_2;
+    const INFINITY: Self = f64::INFINITY;
+    const NAN: Self = f64::NAN;
+    const EPSILON: Self = f64::EPSILON;
+    const SIZE: usize = 8;
+
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_usize(n: usize) -> Self {
+        n as f64
+    }
+
+    fn to_bits_u64(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn write_le(self, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_le(buf: &[u8]) -> Self {
+        f64::from_le_bytes(buf.try_into().unwrap())
+    }
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
+
+    fn max(self, other: Self) -> Self {
+        self.max(other)
+    }
+
+    fn min(self, other: Self) -> Self {
+        self.min(other)
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        self.clamp(min, max)
+    }
+
+    fn fabs(self) -> Self {
+        fabs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        sqrt(self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        hypot(self, other)
+    }
+
+    fn pow(self, exponent: Self) -> Self {
+        pow(self, exponent)
+    }
+
+    fn log(self) -> Self {
+        log(self)
+    }
+
+    fn log10(self) -> Self {
+        log10(self)
+    }
+
+    fn sin(self) -> Self {
+        sin(self)
+    }
+
+    fn cos(self) -> Self {
+        cos(self)
+    }
+
+    fn tan(self) -> Self {
+        tan(self)
+    }
+
+    fn acos(self) -> Self {
+        acos(self)
+    }
+
+    fn atan(self) -> Self {
+        atan(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        atan2(self, other)
+    }
+
+    fn sinh(self) -> Self {
+        sinh(self)
+    }
+
+    fn tanh(self) -> Self {
+        tanh(self)
+    }
+}
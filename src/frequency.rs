@@ -28,8 +28,14 @@
 //!
 //! `x.hz()`, `x.khz()`, `x.mhz()`, `x.dt()` will panic for `f32` if they are negative.
 //!
+//! `Hertz::min_sample_rate(...)` will panic if the margin is not positive.
+//!
+
+use crate::{float::Float, Errors};
+use core::fmt;
 
-use crate::Errors;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Base type for frequency, everything is based on Hertz
 #[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
@@ -50,6 +56,79 @@ pub trait ToHertz<T> {
     fn dt(self) -> Hertz<T>;
 }
 
+// -----------------------------------------------
+// Generic implementation
+// -----------------------------------------------
+
+impl<T: Float> Hertz<T> {
+    pub fn from_hz(hz: T) -> Result<Self, Errors> {
+        if hz > T::ZERO {
+            Ok(Hertz(hz))
+        } else {
+            Err(Errors::NegativeFrequency)
+        }
+    }
+
+    pub fn from_dt(dt: T) -> Result<Self, Errors> {
+        if dt > T::ZERO {
+            Ok(Hertz(T::ONE / dt))
+        } else {
+            Err(Errors::NegativeFrequency)
+        }
+    }
+
+    pub fn hz(self) -> T {
+        self.0
+    }
+
+    /// Limits the frequency to just below the Nyquist frequency of `fs`, leaving frequencies
+    /// below it unchanged. The result is always accepted by `Coefficients::from_params`.
+    pub fn clamp_to_nyquist(&self, fs: Hertz<T>) -> Hertz<T> {
+        let nyquist = fs.0 / T::TWO * (T::ONE - T::EPSILON);
+
+        Hertz(self.0.min(nyquist))
+    }
+
+    /// The smallest sampling frequency for which this frequency is below Nyquist by the factor
+    /// `margin`, i.e. `2 f0 margin`, for validating configurations before designing filters.
+    /// Panics if `margin` is not positive.
+    pub fn min_sample_rate(&self, margin: T) -> Hertz<T> {
+        Hertz::<T>::from_hz(T::TWO * self.0 * margin).unwrap()
+    }
+
+    /// The frequency at which this analog frequency lands after a bilinear transform at `fs`
+    /// without prewarping, `fs / pi * atan(pi f / fs)`. Low frequencies barely move, while high
+    /// frequencies are compressed, with infinity landing on Nyquist.
+    pub fn warped(&self, fs: Hertz<T>) -> Hertz<T> {
+        Hertz(fs.0 / T::PI * T::atan(T::PI * self.0 / fs.0))
+    }
+
+    /// The `(requested, warped)` pairs of `warped` for all frequencies in `freqs`, e.g. for
+    /// placing the tick marks of a frequency axis
+    #[cfg(feature = "alloc")]
+    pub fn warp_table(fs: Hertz<T>, freqs: &[Hertz<T>]) -> Vec<(Hertz<T>, Hertz<T>)> {
+        freqs.iter().map(|&f| (f, f.warped(fs))).collect()
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Display for Hertz<T> {
+    /// Formats the frequency in Hz, kHz or MHz depending on its magnitude. Unless a precision is
+    /// given, Hz are shown as whole numbers and kHz/MHz with one decimal, e.g. `"999 Hz"` and
+    /// `"1.5 kHz"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kilo, mega) = (T::from_f64(1_000.0), T::from_f64(1_000_000.0));
+        let (value, unit, precision) = if self.0 < kilo {
+            (self.0, "Hz", 0)
+        } else if self.0 < mega {
+            (self.0 / kilo, "kHz", 1)
+        } else {
+            (self.0 / mega, "MHz", 1)
+        };
+
+        write!(f, "{:.*} {}", f.precision().unwrap_or(precision), value, unit)
+    }
+}
+
 // -----------------------------------------------
 // f32 implementation
 // -----------------------------------------------
@@ -86,7 +165,7 @@ impl ToHertz<f32> for u32 {
     }
 
     fn dt(self) -> Hertz<f32> {
-        Hertz::<f32>::from_hz(1 as f32 / self as f32).unwrap()
+        Hertz::<f32>::from_hz(1.0 / self as f32).unwrap()
     }
 }
 
@@ -104,29 +183,7 @@ impl ToHertz<f32> for i32 {
     }
 
     fn dt(self) -> Hertz<f32> {
-        Hertz::<f32>::from_hz(1 as f32 / self as f32).unwrap()
-    }
-}
-
-impl Hertz<f32> {
-    pub fn from_hz(hz: f32) -> Result<Self, Errors> {
-        if hz > 0.0 {
-            Ok(Hertz(hz))
-        } else {
-            Err(Errors::NegativeFrequency)
-        }
-    }
-
-    pub fn from_dt(dt: f32) -> Result<Self, Errors> {
-        if dt > 0.0 {
-            Ok(Hertz(1.0 / dt))
-        } else {
-            Err(Errors::NegativeFrequency)
-        }
-    }
-
-    pub fn hz(self) -> f32 {
-        self.0
+        Hertz::<f32>::from_hz(1.0 / self as f32).unwrap()
     }
 }
 
@@ -184,7 +241,7 @@ impl ToHertz<f64> for u32 {
     }
 
     fn dt(self) -> Hertz<f64> {
-        Hertz::<f64>::from_hz(1 as f64 / self as f64).unwrap()
+        Hertz::<f64>::from_hz(1.0 / self as f64).unwrap()
     }
 }
 
@@ -202,28 +259,6 @@ impl ToHertz<f64> for i32 {
     }
 
     fn dt(self) -> Hertz<f64> {
-        Hertz::<f64>::from_hz(1 as f64 / self as f64).unwrap()
-    }
-}
-
-impl Hertz<f64> {
-    pub fn from_hz(hz: f64) -> Result<Self, Errors> {
-        if hz > 0.0 {
-            Ok(Hertz(hz))
-        } else {
-            Err(Errors::NegativeFrequency)
-        }
-    }
-
-    pub fn from_dt(dt: f64) -> Result<Self, Errors> {
-        if dt > 0.0 {
-            Ok(Hertz(1.0 / dt))
-        } else {
-            Err(Errors::NegativeFrequency)
-        }
-    }
-
-    pub fn hz(self) -> f64 {
-        self.0
+        Hertz::<f64>::from_hz(1.0 / self as f64).unwrap()
     }
 }
@@ -0,0 +1,54 @@
+//! # gain
+//!
+//! A helper module for type-safe gains in decibels, to avoid mixing up linear and logarithmic
+//! gains. The gain carrying `Type` variants are generic over the gain, so a `Type<Decibels<T>>`
+//! converts into the `Type<T>` expected by `Coefficients::from_params`, while plain floats keep
+//! working as gains in decibels. The constructors taking a gain or level in decibels as a single
+//! value, such as `Coefficients::band_pass_peak_db`, accept anything converting into `Decibels`.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let gain = Decibels::<f32>::from_linear(2.0);
+//! let coeffs = Coefficients::<f32>::from_params(Type::PeakingEQ(gain).into(), 48.khz(), 1.khz(), 1.0).unwrap();
+//!
+//! let mut biquad = DirectForm1::<f32>::new(coeffs);
+//! let output = biquad.run(1.0);
+//! ```
+
+use crate::{float::Float, Type};
+
+/// A gain in decibels
+#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+pub struct Decibels<T>(pub T);
+
+impl<T: Float> Decibels<T> {
+    /// Creates the gain in decibels of a linear amplitude gain, `20 log10(linear)`
+    pub fn from_linear(linear: T) -> Self {
+        Decibels(T::from_f64(20.0) * T::log10(linear))
+    }
+
+    /// The linear amplitude gain, `10^(dB / 20)`
+    pub fn to_linear(self) -> T {
+        T::pow(T::from_f64(10.0), self.0 / T::from_f64(20.0))
+    }
+
+    /// The gain in decibels as a float
+    pub fn db(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Decibels<T> {
+    fn from(db_gain: T) -> Self {
+        Decibels(db_gain)
+    }
+}
+
+impl<T> From<Type<Decibels<T>>> for Type<T> {
+    fn from(filter: Type<Decibels<T>>) -> Self {
+        filter.map_gain(|db_gain| db_gain.0)
+    }
+}
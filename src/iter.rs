@@ -0,0 +1,84 @@
+//! # iter
+//!
+//! Iterator adapters which lazily filter a stream of samples, so that biquads compose with `map`,
+//! `take` and the other iterator adapters. `Biquad::filter_iter` borrows the filter, which keeps
+//! its state after the iterator is dropped, while `Biquad::into_filter_iter` takes ownership.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//! let mut biquad = DirectForm1::<f32>::new(coeffs);
+//!
+//! let source = (0..100).map(|i| if i % 10 < 5 { 1.0 } else { -1.0 });
+//! let output: Vec<f32> = biquad.filter_iter(source).collect();
+//!
+//! assert_eq!(output.len(), 100);
+//! ```
+
+use crate::Biquad;
+
+/// An iterator filtering the samples of `I` with a borrowed biquad, see `Biquad::filter_iter`
+#[derive(Debug)]
+pub struct FilterIter<'a, B, I> {
+    filter: &'a mut B,
+    source: I,
+}
+
+impl<'a, B, I> FilterIter<'a, B, I> {
+    pub(crate) fn new(filter: &'a mut B, source: I) -> Self {
+        FilterIter { filter, source }
+    }
+}
+
+impl<'a, B, I> Iterator for FilterIter<'a, B, I>
+where
+    I: Iterator,
+    B: Biquad<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.source.next().map(|x| self.filter.run(x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+}
+
+/// An iterator filtering the samples of `I` with an owned biquad, see `Biquad::into_filter_iter`
+#[derive(Clone, Debug)]
+pub struct IntoFilterIter<B, I> {
+    filter: B,
+    source: I,
+}
+
+impl<B, I> IntoFilterIter<B, I> {
+    pub(crate) fn new(filter: B, source: I) -> Self {
+        IntoFilterIter { filter, source }
+    }
+
+    /// Returns the biquad, with the state reached after the samples consumed so far
+    pub fn into_inner(self) -> B {
+        self.filter
+    }
+}
+
+impl<B, I> Iterator for IntoFilterIter<B, I>
+where
+    I: Iterator,
+    B: Biquad<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.source.next().map(|x| self.filter.run(x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+}
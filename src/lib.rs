@@ -11,29 +11,27 @@
 //! # Examples
 //!
 //! ```
-//! fn main() {
-//!     use biquad::*;
+//! use biquad::*;
 //!
-//!     // Cutoff and sampling frequencies
-//!     let f0 = 10.hz();
-//!     let fs = 1.khz();
+//! // Cutoff and sampling frequencies
+//! let f0 = 10.hz();
+//! let fs = 1.khz();
 //!
-//!     // Create coefficients for the biquads
-//!     let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+//! // Create coefficients for the biquads
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
 //!
-//!     // Create two different biquads
-//!     let mut biquad1 = DirectForm1::<f32>::new(coeffs);
-//!     let mut biquad2 = DirectForm2Transposed::<f32>::new(coeffs);
+//! // Create two different biquads
+//! let mut biquad1 = DirectForm1::<f32>::new(coeffs);
+//! let mut biquad2 = DirectForm2Transposed::<f32>::new(coeffs);
 //!
-//!     let input_vec = vec![0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
-//!     let mut output_vec1 = Vec::new();
-//!     let mut output_vec2 = Vec::new();
+//! let input_vec = vec![0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+//! let mut output_vec1 = Vec::new();
+//! let mut output_vec2 = Vec::new();
 //!
-//!     // Run for all the inputs
-//!     for elem in input_vec {
-//!         output_vec1.push(biquad1.run(elem));
-//!         output_vec2.push(biquad2.run(elem));
-//!     }
+//! // Run for all the inputs
+//! for elem in input_vec {
+//!     output_vec1.push(biquad1.run(elem));
+//!     output_vec2.push(biquad2.run(elem));
 //! }
 //! ```
 //!
@@ -43,26 +41,258 @@
 //! [Nyquist Frequency](https://en.wikipedia.org/wiki/Nyquist_frequency), or if the Q value is
 //! negative.
 //!
+//! `Coefficients::try_from_params(...)` additionally errors if the Q value is too small for the
+//! filter type, where `from_params(...)` would return non-finite or degenerate coefficients.
+//!
+//! `Coefficients::from_ba(...)` errors if the filter is of a higher order than two, or if the
+//! leading denominator coefficient is zero.
+//!
+//! `Coefficients::new(...)` errors if a coefficient is not finite or if the poles are outside the
+//! unit circle.
+//!
+//! `CoefficientsBuilder::build()` errors if the filter type, sample rate or cutoff frequency is
+//! not set.
+//!
+//! `Biquad::try_run(...)` errors if the output of the filter is not finite.
+//!
+//! `Coefficients::peaking_gain_at(...)` errors if no center gain reaches the requested gain at
+//! the measurement frequency.
+//!
+//! `Coefficients::from_params_normalized(...)` additionally errors if the gain at the reference
+//! frequency is 0 or not finite.
+//!
+//! `Coefficients::phase_match_allpass(...)` errors if the band is empty or starts above Nyquist.
+//!
+//! `SaturatingBiquad::new(...)` and `SaturatingBiquad::set_drive(...)` error if the drive is not
+//! positive and finite.
+//!
+//! `Cascade::bessel_lowpass(...)`, `Cascade::chebyshev1_lowpass(...)` and
+//! `Cascade::chebyshev1_highpass(...)` error like the `Coefficients` functions creating their
+//! sections, and if the number of stages of the cascade does not match the number of sections.
+//!
 //! `Hertz::from_hz(...)` and `Hertz::from_dt(...)` will error if the frequency is negative.
 //!
 //! # Panics
 //!
 //! `x.hz()`, `x.khz()`, `x.mhz()`, `x.dt()` will panic for `f32`/`f64` if they are negative.
 //!
+//! `Hertz::min_sample_rate(...)` will panic if the margin is not positive.
+//!
+//! `generate_chirp(...)` will panic if a frequency is not positive.
+//!
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// The tests link `std` on their own, see below
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub mod bidirectional;
+pub mod builder;
+pub mod cascade;
+#[cfg(feature = "alloc")]
+pub mod chirp;
 pub mod coefficients;
+pub mod debug;
+pub mod fixed;
+pub mod float;
 pub mod frequency;
+pub mod gain;
+pub mod iter;
+pub mod loudness;
+pub mod multichannel;
+pub mod profile;
+pub mod retunable;
+pub mod saturating;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod tilt;
 
+pub use crate::bidirectional::*;
+pub use crate::builder::*;
+pub use crate::cascade::*;
+#[cfg(feature = "alloc")]
+pub use crate::chirp::*;
 pub use crate::coefficients::*;
+pub use crate::debug::*;
+pub use crate::fixed::*;
+pub use crate::float::*;
 pub use crate::frequency::*;
+pub use crate::gain::*;
+pub use crate::iter::*;
+pub use crate::loudness::*;
+pub use crate::multichannel::*;
+pub use crate::profile::*;
+pub use crate::retunable::*;
+pub use crate::saturating::*;
+#[cfg(feature = "std")]
+pub use crate::stream::*;
+pub use crate::tilt::*;
 
 /// The required functions of a biquad implementation
 pub trait Biquad<T> {
     /// A single iteration of a biquad, applying the filtering on the input
     fn run(&mut self, input: T) -> T;
 
+    /// A single iteration of a biquad, returning both the filtered (wet) and the unfiltered (dry)
+    /// sample as `(wet, dry)` for parallel mixing
+    fn run_wet_dry(&mut self, input: T) -> (T, T)
+    where
+        T: Copy,
+    {
+        (self.run(input), input)
+    }
+
+    /// A single iteration of a biquad like `run`, resulting in an `Err(Errors::NonFiniteOutput)`
+    /// if the output is infinite or NaN, e.g. so that a downstream stage can mute instead of
+    /// passing on garbage. The state is left as is on an `Err()`, and usually needs to be reset
+    /// with `reset_state` before the filter produces finite output again.
+    fn try_run(&mut self, input: T) -> Result<T, Errors>
+    where
+        T: Float,
+    {
+        let output = self.run(input);
+
+        if output.is_finite() {
+            Ok(output)
+        } else {
+            Err(Errors::NonFiniteOutput)
+        }
+    }
+
+    /// Filters `input` into `output`, keeping the state across the block, e.g. for large buffers
+    /// where calling `run` per sample adds up. Panics if the lengths differ.
+    fn process_block(&mut self, input: &[T], output: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(input.len(), output.len(), "output length must match the input length");
+
+        for (out, &x) in output.iter_mut().zip(input) {
+            *out = self.run(x);
+        }
+    }
+
+    /// Filters `buf` in place, keeping the state across the block
+    fn process_block_in_place(&mut self, buf: &mut [T])
+    where
+        T: Copy,
+    {
+        for sample in buf.iter_mut() {
+            *sample = self.run(*sample);
+        }
+    }
+
+    /// Filters `buf` in place, multiplying each filtered sample by the matching gain in `env`,
+    /// e.g. for a fade applied along with the filtering. Panics if the lengths differ.
+    fn run_block_enveloped(&mut self, buf: &mut [T], env: &[T])
+    where
+        T: Copy + core::ops::Mul<Output = T>,
+    {
+        assert_eq!(buf.len(), env.len(), "envelope length must match the block length");
+
+        for (sample, &gain) in buf.iter_mut().zip(env) {
+            *sample = self.run(*sample) * gain;
+        }
+    }
+
+    /// Filters `input` while applying `coeffs[i]` before processing `input[i]`, keeping the
+    /// state across the coefficient changes, e.g. for rendering precomputed automation. Panics if
+    /// the lengths differ.
+    #[cfg(feature = "alloc")]
+    fn run_automated(&mut self, input: &[T], coeffs: &[Coefficients<T>]) -> Vec<T>
+    where
+        T: Copy,
+    {
+        assert_eq!(input.len(), coeffs.len(), "one set of coefficients is needed per sample");
+
+        input
+            .iter()
+            .zip(coeffs)
+            .map(|(&x, &c)| {
+                self.update_coefficients(c);
+                self.run(x)
+            })
+            .collect()
+    }
+
+    /// Runs zeros through the filter to drain the tail left in its state after the last input,
+    /// e.g. the ring of a resonant filter when rendering. Stops once two consecutive outputs are
+    /// below -120 dB, as these determine the remaining free decay of a second order filter, or
+    /// after `max_len` samples.
+    #[cfg(feature = "alloc")]
+    fn flush(&mut self, max_len: usize) -> Vec<T>
+    where
+        T: Float,
+    {
+        let threshold = T::from_f64(1e-6);
+        let mut tail = Vec::new();
+        let mut quiet = 0;
+
+        while tail.len() < max_len && quiet < 2 {
+            let out = self.run(T::ZERO);
+            quiet = if T::fabs(out) < threshold { quiet + 1 } else { 0 };
+            tail.push(out);
+        }
+
+        tail
+    }
+
+    /// The first `n` samples of the impulse response of the filter. The response is computed on a
+    /// copy of the filter with its state reset, so the state of `self` is left untouched.
+    #[cfg(feature = "alloc")]
+    fn impulse_response(&self, n: usize) -> Vec<T>
+    where
+        Self: Clone,
+        T: Float,
+    {
+        let mut filter = self.clone();
+        filter.reset_state();
+
+        (0..n)
+            .map(|i| filter.run(if i == 0 { T::ONE } else { T::ZERO }))
+            .collect()
+    }
+
+    /// The first `n` samples of the step response of the filter. The response is computed on a
+    /// copy of the filter with its state reset, so the state of `self` is left untouched.
+    #[cfg(feature = "alloc")]
+    fn step_response(&self, n: usize) -> Vec<T>
+    where
+        Self: Clone,
+        T: Float,
+    {
+        let mut filter = self.clone();
+        filter.reset_state();
+
+        (0..n).map(|_| filter.run(T::ONE)).collect()
+    }
+
+    /// Lazily filters the samples of `source`, borrowing the filter so its state is kept after
+    /// the iterator is dropped
+    fn filter_iter<I>(&mut self, source: I) -> FilterIter<'_, Self, I>
+    where
+        Self: Sized,
+        I: Iterator<Item = T>,
+    {
+        FilterIter::new(self, source)
+    }
+
+    /// Lazily filters the samples of `source`, taking ownership of the filter
+    fn into_filter_iter<I>(self, source: I) -> IntoFilterIter<Self, I>
+    where
+        Self: Sized,
+        I: Iterator<Item = T>,
+    {
+        IntoFilterIter::new(self, source)
+    }
+
     /// Updating of coefficients
     fn update_coefficients(&mut self, new_coefficients: Coefficients<T>);
 
@@ -72,6 +302,12 @@ pub trait Biquad<T> {
 
     /// Set the internal state of the biquad to 0 without allocation.
     fn reset_state(&mut self);
+
+    /// The processing latency of the filter in samples, for delay compensation by a host. Biquads
+    /// have no algorithmic latency, so this is 0 unless a wrapper adds latency of its own.
+    fn latency_samples(&self) -> usize {
+        0
+    }
 }
 
 /// Possible errors
@@ -80,6 +316,39 @@ pub enum Errors {
     OutsideNyquist,
     NegativeQ,
     NegativeFrequency,
+    QTooSmall,
+    InvalidEncoding,
+    OrderTooHigh,
+    InvalidCoefficients,
+    Unstable,
+    MissingParameter,
+    NonFiniteOutput,
+    GainUnreachable,
+    InvalidDrive,
+    StageCountMismatch,
+}
+
+impl Errors {
+    /// Whether the error can be recovered from by clamping the offending parameter into its
+    /// valid range and retrying, such as a cutoff above Nyquist or a too small Q. Errors from
+    /// malformed input data, like a negative frequency or an invalid encoding, are not.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Errors::OutsideNyquist
+            | Errors::NegativeQ
+            | Errors::QTooSmall
+            | Errors::InvalidDrive => true,
+            Errors::NegativeFrequency
+            | Errors::InvalidEncoding
+            | Errors::OrderTooHigh
+            | Errors::InvalidCoefficients
+            | Errors::Unstable
+            | Errors::MissingParameter
+            | Errors::NonFiniteOutput
+            | Errors::GainUnreachable
+            | Errors::StageCountMismatch => false,
+        }
+    }
 }
 
 /// Internal states and coefficients of the Direct Form 1 form
@@ -92,6 +361,24 @@ pub struct DirectForm1<T> {
     coeffs: Coefficients<T>,
 }
 
+/// The internal state of a `DirectForm1`, the last two inputs and outputs, for pausing and
+/// resuming a running filter
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FilterState<T> {
+    pub x1: T,
+    pub x2: T,
+    pub y1: T,
+    pub y2: T,
+}
+
+/// The internal state of a `DirectForm2Transposed`, its two state variables, for pausing and
+/// resuming a running filter
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TransposedState<T> {
+    pub s1: T,
+    pub s2: T,
+}
+
 /// Internal states and coefficients of the Direct Form 2 Transposed form
 #[derive(Copy, Clone, Debug)]
 pub struct DirectForm2Transposed<T> {
@@ -100,64 +387,41 @@ pub struct DirectForm2Transposed<T> {
     coeffs: Coefficients<T>,
 }
 
-impl DirectForm1<f32> {
+impl<T: Float> DirectForm1<T> {
     /// Creates a Direct Form 1 biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients<f32>) -> Self {
+    pub fn new(coefficients: Coefficients<T>) -> Self {
         DirectForm1 {
-            y1: 0.0_f32,
-            y2: 0.0_f32,
-            x1: 0.0_f32,
-            x2: 0.0_f32,
+            y1: T::ZERO,
+            y2: T::ZERO,
+            x1: T::ZERO,
+            x2: T::ZERO,
             coeffs: coefficients,
         }
     }
 }
 
-impl Biquad<f32> for DirectForm1<f32> {
-    fn run(&mut self, input: f32) -> f32 {
-        let out = self.coeffs.b0 * input + self.coeffs.b1 * self.x1 + self.coeffs.b2 * self.x2
-            - self.coeffs.a1 * self.y1
-            - self.coeffs.a2 * self.y2;
-
-        self.x2 = self.x1;
-        self.x1 = input;
-        self.y2 = self.y1;
-        self.y1 = out;
-
-        out
-    }
-
-    fn update_coefficients(&mut self, new_coefficients: Coefficients<f32>) {
-        self.coeffs = new_coefficients;
-    }
-
-    fn replace_coefficients(&mut self, new_coefficients: Coefficients<f32>) -> Coefficients<f32> {
-        core::mem::replace(&mut self.coeffs, new_coefficients)
-    }
-
-    fn reset_state(&mut self) {
-        self.x1 = 0.;
-        self.x2 = 0.;
-        self.y1 = 0.;
-        self.y2 = 0.;
+impl<T: Copy> DirectForm1<T> {
+    /// The internal state, which together with the coefficients fully determines the output
+    pub fn get_state(&self) -> FilterState<T> {
+        FilterState {
+            x1: self.x1,
+            x2: self.x2,
+            y1: self.y1,
+            y2: self.y2,
+        }
     }
-}
 
-impl DirectForm1<f64> {
-    /// Creates a Direct Form 1 biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients<f64>) -> Self {
-        DirectForm1 {
-            y1: 0.0_f64,
-            y2: 0.0_f64,
-            x1: 0.0_f64,
-            x2: 0.0_f64,
-            coeffs: coefficients,
-        }
+    /// Sets the internal state, e.g. to one saved by `get_state` to continue a paused stream
+    pub fn set_state(&mut self, state: FilterState<T>) {
+        self.x1 = state.x1;
+        self.x2 = state.x2;
+        self.y1 = state.y1;
+        self.y2 = state.y2;
     }
 }
 
-impl Biquad<f64> for DirectForm1<f64> {
-    fn run(&mut self, input: f64) -> f64 {
+impl<T: Float> Biquad<T> for DirectForm1<T> {
+    fn run(&mut self, input: T) -> T {
         let out = self.coeffs.b0 * input + self.coeffs.b1 * self.x1 + self.coeffs.b2 * self.x2
             - self.coeffs.a1 * self.y1
             - self.coeffs.a2 * self.y2;
@@ -170,35 +434,51 @@ impl Biquad<f64> for DirectForm1<f64> {
         out
     }
 
-    fn update_coefficients(&mut self, new_coefficients: Coefficients<f64>) {
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
         self.coeffs = new_coefficients;
     }
 
-    fn replace_coefficients(&mut self, new_coefficients: Coefficients<f64>) -> Coefficients<f64> {
+    fn replace_coefficients(&mut self, new_coefficients: Coefficients<T>) -> Coefficients<T> {
         core::mem::replace(&mut self.coeffs, new_coefficients)
     }
 
     fn reset_state(&mut self) {
-        self.x1 = 0.;
-        self.x2 = 0.;
-        self.y1 = 0.;
-        self.y2 = 0.;
+        self.x1 = T::ZERO;
+        self.x2 = T::ZERO;
+        self.y1 = T::ZERO;
+        self.y2 = T::ZERO;
     }
 }
 
-impl DirectForm2Transposed<f32> {
+impl<T: Float> DirectForm2Transposed<T> {
     /// Creates a Direct Form 2 Transposed biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients<f32>) -> Self {
+    pub fn new(coefficients: Coefficients<T>) -> Self {
         DirectForm2Transposed {
-            s1: 0.0_f32,
-            s2: 0.0_f32,
+            s1: T::ZERO,
+            s2: T::ZERO,
             coeffs: coefficients,
         }
     }
 }
 
-impl Biquad<f32> for DirectForm2Transposed<f32> {
-    fn run(&mut self, input: f32) -> f32 {
+impl<T: Copy> DirectForm2Transposed<T> {
+    /// The internal state, which together with the coefficients fully determines the output
+    pub fn get_state(&self) -> TransposedState<T> {
+        TransposedState {
+            s1: self.s1,
+            s2: self.s2,
+        }
+    }
+
+    /// Sets the internal state, e.g. to one saved by `get_state` to continue a paused stream
+    pub fn set_state(&mut self, state: TransposedState<T>) {
+        self.s1 = state.s1;
+        self.s2 = state.s2;
+    }
+}
+
+impl<T: Float> Biquad<T> for DirectForm2Transposed<T> {
+    fn run(&mut self, input: T) -> T {
         let out = self.s1 + self.coeffs.b0 * input;
         self.s1 = self.s2 + self.coeffs.b1 * input - self.coeffs.a1 * out;
         self.s2 = self.coeffs.b2 * input - self.coeffs.a2 * out;
@@ -206,51 +486,53 @@ impl Biquad<f32> for DirectForm2Transposed<f32> {
         out
     }
 
-    fn update_coefficients(&mut self, new_coefficients: Coefficients<f32>) {
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
         self.coeffs = new_coefficients;
     }
 
-    fn replace_coefficients(&mut self, new_coefficients: Coefficients<f32>) -> Coefficients<f32> {
+    fn replace_coefficients(&mut self, new_coefficients: Coefficients<T>) -> Coefficients<T> {
         core::mem::replace(&mut self.coeffs, new_coefficients)
     }
 
     fn reset_state(&mut self) {
-        self.s1 = 0.;
-        self.s2 = 0.;
-    }
-}
-
-impl DirectForm2Transposed<f64> {
-    /// Creates a Direct Form 2 Transposed biquad from a set of filter coefficients
-    pub fn new(coefficients: Coefficients<f64>) -> Self {
-        DirectForm2Transposed {
-            s1: 0.0_f64,
-            s2: 0.0_f64,
-            coeffs: coefficients,
-        }
+        self.s1 = T::ZERO;
+        self.s2 = T::ZERO;
     }
 }
 
-impl Biquad<f64> for DirectForm2Transposed<f64> {
-    fn run(&mut self, input: f64) -> f64 {
-        let out = self.s1 + self.coeffs.b0 * input;
-        self.s1 = self.s2 + self.coeffs.b1 * input - self.coeffs.a1 * out;
-        self.s2 = self.coeffs.b2 * input - self.coeffs.a2 * out;
+impl DirectForm2Transposed<f32> {
+    /// Filters `buf` in place, two samples at a time. The state space recurrence is unrolled over
+    /// two samples, which makes both outputs and both new states independent of each other, so
+    /// they can be computed in parallel by SIMD or superscalar execution instead of waiting on
+    /// the previous sample. The results are not bit exact to sample by sample processing, since
+    /// the operations are regrouped, and the rounding differences grow with the resonance of the
+    /// filter, staying within a small multiple of `f32::EPSILON` times the peak output. The speedup
+    /// over calling `run` per sample depends on the target, so benchmark both when it matters.
+    /// Used by `filter_stream`.
+    #[cfg(any(feature = "std", test))]
+    pub(crate) fn run_block(&mut self, buf: &mut [f32]) {
+        let Coefficients { a1, a2, b0, b1, b2 } = self.coeffs;
 
-        out
-    }
+        // State space form s' = A s + B u, y = s1 + b0 u, with A = [[-a1, 1], [-a2, 0]]
+        let (bu1, bu2) = (b1 - a1 * b0, b2 - a2 * b0);
+        // Two samples ahead: A^2 and A B
+        let (aa11, aa12, aa21, aa22) = (a1 * a1 - a2, -a1, a1 * a2, -a2);
+        let (ab1, ab2) = (bu2 - a1 * bu1, -a2 * bu1);
 
-    fn update_coefficients(&mut self, new_coefficients: Coefficients<f64>) {
-        self.coeffs = new_coefficients;
-    }
+        let mut pairs = buf.chunks_exact_mut(2);
+        for pair in &mut pairs {
+            let (u0, u1) = (pair[0], pair[1]);
+            let (s1, s2) = (self.s1, self.s2);
 
-    fn replace_coefficients(&mut self, new_coefficients: Coefficients<f64>) -> Coefficients<f64> {
-        core::mem::replace(&mut self.coeffs, new_coefficients)
-    }
+            pair[0] = s1 + b0 * u0;
+            pair[1] = (s2 - a1 * s1) + bu1 * u0 + b0 * u1;
+            self.s1 = (aa11 * s1 + aa12 * s2) + ab1 * u0 + bu1 * u1;
+            self.s2 = (aa21 * s1 + aa22 * s2) + ab2 * u0 + bu2 * u1;
+        }
 
-    fn reset_state(&mut self) {
-        self.s1 = 0.;
-        self.s2 = 0.;
+        for sample in pairs.into_remainder() {
+            *sample = self.run(*sample);
+        }
     }
 }
 
@@ -466,4 +748,5930 @@ mod tests {
             output_vec2.push(biquad2.run(elem));
         }
     }
+
+    #[test]
+    fn test_phase_match_allpass_f32() {
+        let fs = 48.khz();
+        let target =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+        let band = (1.khz(), 8.khz());
+        let corrector = Coefficients::<f32>::phase_match_allpass(&target, fs, band).unwrap();
+
+        // Spread of the group delay across the band, a flat phase is a pure delay
+        let spread = |delays: &dyn Fn(f32) -> f32| {
+            let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+            for i in 0..64 {
+                let f = 1000.0 * libm::powf(8.0, i as f32 / 63.0);
+                let d = delays(2.0 * core::f32::consts::PI * f / fs.hz());
+                min = min.min(d);
+                max = max.max(d);
+            }
+            max - min
+        };
+
+        let alone = spread(&|w| target.group_delay_at(w));
+        let combined = spread(&|w| target.group_delay_at(w) + corrector.group_delay_at(w));
+
+        assert!(combined < alone);
+
+        let allpass = |band| Coefficients::<f32>::phase_match_allpass(&target, fs, band);
+        assert_eq!(allpass((8.khz(), 1.khz())), Err(Errors::NegativeFrequency));
+        assert_eq!(allpass((1.khz(), 1.khz())), Err(Errors::NegativeFrequency));
+        assert_eq!(allpass((24.khz(), 30.khz())), Err(Errors::OutsideNyquist));
+    }
+
+    #[test]
+    fn test_phase_match_allpass_f64() {
+        let fs = 48.khz();
+        let target =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64).unwrap();
+        let band = (1.khz(), 8.khz());
+        let corrector = Coefficients::<f64>::phase_match_allpass(&target, fs, band).unwrap();
+
+        // Spread of the group delay across the band, a flat phase is a pure delay
+        let spread = |delays: &dyn Fn(f64) -> f64| {
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for i in 0..64 {
+                let f = 1000.0 * libm::pow(8.0, i as f64 / 63.0);
+                let d = delays(2.0 * core::f64::consts::PI * f / fs.hz());
+                min = min.min(d);
+                max = max.max(d);
+            }
+            max - min
+        };
+
+        let alone = spread(&|w| target.group_delay_at(w));
+        let combined = spread(&|w| target.group_delay_at(w) + corrector.group_delay_at(w));
+
+        assert!(combined < alone);
+
+        let allpass = |band| Coefficients::<f64>::phase_match_allpass(&target, fs, band);
+        assert_eq!(allpass((8.khz(), 1.khz())), Err(Errors::NegativeFrequency));
+        assert_eq!(allpass((1.khz(), 1.khz())), Err(Errors::NegativeFrequency));
+        assert_eq!(allpass((24.khz(), 30.khz())), Err(Errors::OutsideNyquist));
+    }
+
+    #[test]
+    fn test_hertz_display_f32() {
+        assert_eq!(format!("{}", Hertz::<f32>::from_hz(999.0).unwrap()), "999 Hz");
+        assert_eq!(format!("{}", Hertz::<f32>::from_hz(1_000.0).unwrap()), "1.0 kHz");
+        assert_eq!(format!("{}", Hertz::<f32>::from_hz(1_500.0).unwrap()), "1.5 kHz");
+        assert_eq!(format!("{}", Hertz::<f32>::from_hz(20_000.0).unwrap()), "20.0 kHz");
+        assert_eq!(format!("{}", Hertz::<f32>::from_hz(2_500_000.0).unwrap()), "2.5 MHz");
+        assert_eq!(format!("{:.2}", Hertz::<f32>::from_hz(1_234.0).unwrap()), "1.23 kHz");
+    }
+
+    #[test]
+    fn test_hertz_display_f64() {
+        assert_eq!(format!("{}", Hertz::<f64>::from_hz(999.0).unwrap()), "999 Hz");
+        assert_eq!(format!("{}", Hertz::<f64>::from_hz(1_000.0).unwrap()), "1.0 kHz");
+        assert_eq!(format!("{}", Hertz::<f64>::from_hz(1_500.0).unwrap()), "1.5 kHz");
+        assert_eq!(format!("{}", Hertz::<f64>::from_hz(20_000.0).unwrap()), "20.0 kHz");
+        assert_eq!(format!("{}", Hertz::<f64>::from_hz(2_500_000.0).unwrap()), "2.5 MHz");
+        assert_eq!(format!("{:.2}", Hertz::<f64>::from_hz(1_234.0).unwrap()), "1.23 kHz");
+    }
+
+    #[test]
+    fn test_saturating_biquad_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut linear = DirectForm1::<f32>::new(coeffs);
+        let mut saturating =
+            SaturatingBiquad::<_, f32>::new(DirectForm1::<f32>::new(coeffs), 1.0).unwrap();
+
+        // Low level signals pass nearly linearly
+        for _ in 0..1000 {
+            let reference = linear.run(0.01);
+            let output = saturating.run(0.01);
+            assert!((output - reference).abs() <= 1e-4 * reference.abs());
+        }
+
+        linear.reset_state();
+        saturating.reset_state();
+
+        // High level signals are compressed, and bounded by 1 / drive
+        for _ in 0..1000 {
+            let reference = linear.run(10.0);
+            let output = saturating.run(10.0);
+            assert!(output.abs() <= reference.abs());
+            assert!(output.abs() <= 1.0);
+        }
+
+        // A drive which is not positive results in NaN or inverted outputs, and is rejected
+        let biquad = DirectForm1::<f32>::new(coeffs);
+        for &drive in &[0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                SaturatingBiquad::<_, f32>::new(biquad, drive).err(),
+                Some(Errors::InvalidDrive)
+            );
+            assert_eq!(saturating.set_drive(drive), Err(Errors::InvalidDrive));
+        }
+        assert_eq!(saturating.drive(), 1.0);
+        assert_eq!(saturating.set_drive(2.0), Ok(()));
+        assert_eq!(saturating.drive(), 2.0);
+    }
+
+    #[test]
+    fn test_saturating_biquad_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut linear = DirectForm1::<f64>::new(coeffs);
+        let mut saturating =
+            SaturatingBiquad::<_, f64>::new(DirectForm1::<f64>::new(coeffs), 1.0).unwrap();
+
+        // Low level signals pass nearly linearly
+        for _ in 0..1000 {
+            let reference = linear.run(0.01);
+            let output = saturating.run(0.01);
+            assert!((output - reference).abs() <= 1e-4 * reference.abs());
+        }
+
+        linear.reset_state();
+        saturating.reset_state();
+
+        // High level signals are compressed, and bounded by 1 / drive
+        for _ in 0..1000 {
+            let reference = linear.run(10.0);
+            let output = saturating.run(10.0);
+            assert!(output.abs() <= reference.abs());
+            assert!(output.abs() <= 1.0);
+        }
+
+        // A drive which is not positive results in NaN or inverted outputs, and is rejected
+        let biquad = DirectForm1::<f64>::new(coeffs);
+        for &drive in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                SaturatingBiquad::<_, f64>::new(biquad, drive).err(),
+                Some(Errors::InvalidDrive)
+            );
+            assert_eq!(saturating.set_drive(drive), Err(Errors::InvalidDrive));
+        }
+        assert_eq!(saturating.drive(), 1.0);
+        assert_eq!(saturating.set_drive(2.0), Ok(()));
+        assert_eq!(saturating.drive(), 2.0);
+    }
+
+    #[test]
+    fn test_fixed_q1_30_f32() {
+        let fs = 48.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = libm::sinf(2.0 * core::f32::consts::PI * 500.0 * i as f32 / fs.hz());
+            let input = (x * (1 << 20) as f32) as i32;
+
+            let expected = reference.run(input as f32) as f64;
+            let output = fixed.run(input) as f64;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 90.0, "SNR {} dB", snr);
+    }
+
+    #[test]
+    fn test_fixed_q1_30_f64() {
+        let fs = 48.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64).unwrap();
+
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = libm::sin(2.0 * core::f64::consts::PI * 500.0 * i as f64 / fs.hz());
+            let input = (x * (1 << 20) as f64) as i32;
+
+            let expected = reference.run(input as f64) as f64;
+            let output = fixed.run(input) as f64;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 90.0, "SNR {} dB", snr);
+    }
+
+    #[test]
+    fn test_to_fixed_q1_31_f32() {
+        let coeffs = Coefficients::<f32> {
+            a1: -1.0,
+            a2: 0.25,
+            b0: 0.5,
+            b1: 1.0,
+            b2: -0.75,
+        };
+
+        // The largest format, where 1.0 is just out of range and saturates
+        let fixed = coeffs.to_fixed::<31>();
+        assert_eq!(fixed.a1, i32::MIN);
+        assert_eq!(fixed.a2, 1 << 29);
+        assert_eq!(fixed.b0, 1 << 30);
+        assert_eq!(fixed.b1, i32::MAX);
+        assert_eq!(fixed.b2, -3 << 29);
+    }
+
+    #[test]
+    fn test_to_fixed_q1_31_f64() {
+        let coeffs = Coefficients::<f64> {
+            a1: -1.0,
+            a2: 0.25,
+            b0: 0.5,
+            b1: 1.0,
+            b2: -0.75,
+        };
+
+        // The largest format, where 1.0 is just out of range and saturates
+        let fixed = coeffs.to_fixed::<31>();
+        assert_eq!(fixed.a1, i32::MIN);
+        assert_eq!(fixed.a2, 1 << 29);
+        assert_eq!(fixed.b0, 1 << 30);
+        assert_eq!(fixed.b1, i32::MAX);
+        assert_eq!(fixed.b2, -3 << 29);
+    }
+
+    #[test]
+    fn test_is_identity_f32() {
+        let fs = 48.khz();
+
+        let flat = Coefficients::<f32>::from_params(Type::PeakingEQ(0.0), fs, 1.khz(), 1.0).unwrap();
+        assert!(flat.is_identity(1e-6));
+
+        let lowpass =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+        assert!(!lowpass.is_identity(1e-6));
+    }
+
+    #[test]
+    fn test_is_identity_f64() {
+        let fs = 48.khz();
+
+        let flat = Coefficients::<f64>::from_params(Type::PeakingEQ(0.0), fs, 1.khz(), 1.0).unwrap();
+        assert!(flat.is_identity(1e-6));
+
+        let lowpass =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64).unwrap();
+        assert!(!lowpass.is_identity(1e-6));
+    }
+
+    #[test]
+    fn test_estimate_q_f32() {
+        let fs = 48.khz();
+
+        for &q in &[0.5, Q_BUTTERWORTH_F32, 2.5, 10.0] {
+            let coeffs = Coefficients::<f32>::from_params(Type::BandPass, fs, 1.khz(), q).unwrap();
+            let estimate = coeffs.estimate_q().unwrap();
+            assert!((estimate - q).abs() < 1e-3 * q, "{} != {}", estimate, q);
+        }
+
+        let single_pole =
+            Coefficients::<f32>::from_params(Type::SinglePoleLowPass, fs, 1.khz(), 0.0).unwrap();
+        assert_eq!(single_pole.estimate_q(), None);
+    }
+
+    #[test]
+    fn test_estimate_q_f64() {
+        let fs = 48.khz();
+
+        for &q in &[0.5, Q_BUTTERWORTH_F64, 2.5, 10.0] {
+            let coeffs = Coefficients::<f64>::from_params(Type::BandPass, fs, 1.khz(), q).unwrap();
+            let estimate = coeffs.estimate_q().unwrap();
+            assert!((estimate - q).abs() < 1e-3 * q, "{} != {}", estimate, q);
+        }
+
+        let single_pole =
+            Coefficients::<f64>::from_params(Type::SinglePoleLowPass, fs, 1.khz(), 0.0).unwrap();
+        assert_eq!(single_pole.estimate_q(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bidirectional_independent_states_f32() {
+        use std::vec::Vec;
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 50.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut bidirectional = BidirectionalBiquad::new(DirectForm1::<f32>::new(coeffs));
+        let mut forward_ref = DirectForm1::<f32>::new(coeffs);
+        let mut backward_ref = DirectForm1::<f32>::new(coeffs);
+
+        let x: Vec<f32> = (0..64).map(|i| if i % 8 == 0 { 1.0 } else { 0.0 }).collect();
+        let y: Vec<f32> = (0..64).map(|i| i as f32 / 64.0).collect();
+
+        // Run two consecutive blocks, the states must carry over without interfering
+        for _ in 0..2 {
+            let (forward, backward) = bidirectional.run_block_bidirectional(&x, &y);
+
+            let expected_forward: Vec<f32> = x.iter().map(|&v| forward_ref.run(v)).collect();
+            let mut expected_backward: Vec<f32> =
+                y.iter().rev().map(|&v| backward_ref.run(v)).collect();
+            expected_backward.reverse();
+
+            assert_eq!(forward, expected_forward);
+            assert_eq!(backward, expected_backward);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bidirectional_independent_states_f64() {
+        use std::vec::Vec;
+
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 1.khz(), 50.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut bidirectional = BidirectionalBiquad::new(DirectForm1::<f64>::new(coeffs));
+        let mut forward_ref = DirectForm1::<f64>::new(coeffs);
+        let mut backward_ref = DirectForm1::<f64>::new(coeffs);
+
+        let x: Vec<f64> = (0..64).map(|i| if i % 8 == 0 { 1.0 } else { 0.0 }).collect();
+        let y: Vec<f64> = (0..64).map(|i| i as f64 / 64.0).collect();
+
+        // Run two consecutive blocks, the states must carry over without interfering
+        for _ in 0..2 {
+            let (forward, backward) = bidirectional.run_block_bidirectional(&x, &y);
+
+            let expected_forward: Vec<f64> = x.iter().map(|&v| forward_ref.run(v)).collect();
+            let mut expected_backward: Vec<f64> =
+                y.iter().rev().map(|&v| backward_ref.run(v)).collect();
+            expected_backward.reverse();
+
+            assert_eq!(forward, expected_forward);
+            assert_eq!(backward, expected_backward);
+        }
+    }
+
+    #[test]
+    fn test_damping_ratio_f32() {
+        let zeta = q_to_damping(Q_BUTTERWORTH_F32);
+        assert!((zeta - 0.707).abs() < 1e-3);
+        assert!((damping_to_q(zeta) - Q_BUTTERWORTH_F32).abs() < 1e-6);
+
+        let fs = 1.khz();
+        let f0 = 10.hz();
+        let from_q = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 2.0).unwrap();
+        let from_zeta = Coefficients::<f32>::from_params_damping(Type::LowPass, fs, f0, 0.25).unwrap();
+        assert_eq!(from_q.a1, from_zeta.a1);
+        assert_eq!(from_q.a2, from_zeta.a2);
+        assert_eq!(from_q.b0, from_zeta.b0);
+
+        let negative = Coefficients::<f32>::from_params_damping(Type::LowPass, fs, f0, -0.5);
+        assert_eq!(negative.unwrap_err(), Errors::NegativeQ);
+    }
+
+    #[test]
+    fn test_damping_ratio_f64() {
+        let zeta = q_to_damping(Q_BUTTERWORTH_F64);
+        assert!((zeta - 0.707).abs() < 1e-3);
+        assert!((damping_to_q(zeta) - Q_BUTTERWORTH_F64).abs() < 1e-6);
+
+        let fs = 1.khz();
+        let f0 = 10.hz();
+        let from_q = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 2.0).unwrap();
+        let from_zeta = Coefficients::<f64>::from_params_damping(Type::LowPass, fs, f0, 0.25).unwrap();
+        assert_eq!(from_q.a1, from_zeta.a1);
+        assert_eq!(from_q.a2, from_zeta.a2);
+        assert_eq!(from_q.b0, from_zeta.b0);
+
+        let negative = Coefficients::<f64>::from_params_damping(Type::LowPass, fs, f0, -0.5);
+        assert_eq!(negative.unwrap_err(), Errors::NegativeQ);
+    }
+
+    #[test]
+    fn test_fir_approx_error_f32() {
+        let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 50.hz(), 0.5).unwrap();
+
+        // Deterministic pseudo random test signal
+        let mut seed = 12345u32;
+        let signal: std::vec::Vec<f32> = (0..512)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as f32 / 32768.0 - 1.0
+            })
+            .collect();
+
+        let mut previous = f32::INFINITY;
+        for &taps in &[2, 4, 8, 16, 32, 64, 128] {
+            let error = coeffs.fir_approx_error(taps, &signal);
+            assert!(error < previous);
+            previous = error;
+        }
+        assert!(previous < 1e-3);
+    }
+
+    #[test]
+    fn test_fir_approx_error_f64() {
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, 1.khz(), 50.hz(), 0.5).unwrap();
+
+        // Deterministic pseudo random test signal
+        let mut seed = 12345u32;
+        let signal: std::vec::Vec<f64> = (0..512)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as f64 / 32768.0 - 1.0
+            })
+            .collect();
+
+        let mut previous = f64::INFINITY;
+        for &taps in &[2, 4, 8, 16, 32, 64, 128] {
+            let error = coeffs.fir_approx_error(taps, &signal);
+            assert!(error < previous);
+            previous = error;
+        }
+        assert!(previous < 1e-3);
+    }
+
+    #[test]
+    fn test_resonant_frequency_f32() {
+        let fs = 48.khz();
+        let f0 = 1.khz();
+
+        let resonant = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 5.0).unwrap();
+        let peak = resonant.resonant_frequency(fs).unwrap();
+        assert!(peak < f0);
+        assert!(peak.hz() > 0.98 * f0.hz());
+
+        let butterworth =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert_eq!(butterworth.resonant_frequency(fs), None);
+    }
+
+    #[test]
+    fn test_resonant_frequency_f64() {
+        let fs = 48.khz();
+        let f0 = 1.khz();
+
+        let resonant = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 5.0).unwrap();
+        let peak = resonant.resonant_frequency(fs).unwrap();
+        assert!(peak < f0);
+        assert!(peak.hz() > 0.98 * f0.hz());
+
+        let butterworth =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert_eq!(butterworth.resonant_frequency(fs), None);
+    }
+
+    #[test]
+    fn test_from_pole_f32() {
+        let (r, theta) = (0.9, 0.3);
+
+        for &kind in &[NumeratorKind::Resonator, NumeratorKind::AllPass] {
+            let coeffs = Coefficients::<f32>::from_pole(r, theta, kind);
+            let poles = coeffs.poles();
+
+            for &(re, im) in &poles {
+                assert!((libm::hypotf(re, im) - r).abs() < 1e-6);
+                assert!((libm::atan2f(im, re).abs() - theta).abs() < 1e-5);
+            }
+            assert_eq!(poles[0].1, -poles[1].1);
+        }
+
+        // The allpass numerator mirrors the denominator
+        let allpass = Coefficients::<f32>::from_pole(r, theta, NumeratorKind::AllPass);
+        assert_eq!(allpass.b0, allpass.a2);
+        assert_eq!(allpass.b1, allpass.a1);
+        assert_eq!(allpass.b2, 1.0);
+    }
+
+    #[test]
+    fn test_from_pole_f64() {
+        let (r, theta) = (0.9, 0.3);
+
+        for &kind in &[NumeratorKind::Resonator, NumeratorKind::AllPass] {
+            let coeffs = Coefficients::<f64>::from_pole(r, theta, kind);
+            let poles = coeffs.poles();
+
+            for &(re, im) in &poles {
+                assert!((libm::hypot(re, im) - r).abs() < 1e-6);
+                assert!((libm::atan2(im, re).abs() - theta).abs() < 1e-5);
+            }
+            assert_eq!(poles[0].1, -poles[1].1);
+        }
+
+        // The allpass numerator mirrors the denominator
+        let allpass = Coefficients::<f64>::from_pole(r, theta, NumeratorKind::AllPass);
+        assert_eq!(allpass.b0, allpass.a2);
+        assert_eq!(allpass.b1, allpass.a1);
+        assert_eq!(allpass.b2, 1.0);
+    }
+
+    #[test]
+    fn test_per_channel_biquad_f32() {
+        let fs = 1.khz();
+        let slow =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let fast =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 100.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut per_channel = PerChannelBiquad::new([
+            DirectForm1::<f32>::new(slow),
+            DirectForm1::<f32>::new(fast),
+        ]);
+        let mut slow_ref = DirectForm1::<f32>::new(slow);
+        let mut fast_ref = DirectForm1::<f32>::new(fast);
+
+        for i in 0..100 {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            let [a, b] = per_channel.run([x, x]);
+            assert_eq!(a, slow_ref.run(x));
+            assert_eq!(b, fast_ref.run(x));
+            if i > 0 {
+                assert!(a != b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_per_channel_biquad_f64() {
+        let fs = 1.khz();
+        let slow =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let fast =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 100.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut per_channel = PerChannelBiquad::new([
+            DirectForm1::<f64>::new(slow),
+            DirectForm1::<f64>::new(fast),
+        ]);
+        let mut slow_ref = DirectForm1::<f64>::new(slow);
+        let mut fast_ref = DirectForm1::<f64>::new(fast);
+
+        for i in 0..100 {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            let [a, b] = per_channel.run([x, x]);
+            assert_eq!(a, slow_ref.run(x));
+            assert_eq!(b, fast_ref.run(x));
+            if i > 0 {
+                assert!(a != b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_coefficients_fail_zero_q_f32() {
+        let f0 = 10.hz();
+        let fs = 1.khz();
+
+        let coeffs = Coefficients::<f32>::try_from_params(Type::LowPass, fs, f0, 0.0);
+        assert_eq!(coeffs.unwrap_err(), Errors::QTooSmall);
+
+        let coeffs = Coefficients::<f32>::try_from_params(Type::LowPass, fs, f0, f32::NAN);
+        assert_eq!(coeffs.unwrap_err(), Errors::QTooSmall);
+
+        let coeffs = Coefficients::<f32>::try_from_params(Type::LowPass, fs, f0, -1.0);
+        assert_eq!(coeffs.unwrap_err(), Errors::NegativeQ);
+
+        // The single pole filters do not use Q
+        let coeffs = Coefficients::<f32>::try_from_params(Type::SinglePoleLowPass, fs, f0, 0.0);
+        assert!(coeffs.is_ok());
+
+        let coeffs =
+            Coefficients::<f32>::try_from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(coeffs.a1.is_finite() && coeffs.a2.is_finite() && coeffs.b0.is_finite());
+    }
+
+    #[test]
+    fn test_coefficients_fail_zero_q_f64() {
+        let f0 = 10.hz();
+        let fs = 1.khz();
+
+        let coeffs = Coefficients::<f64>::try_from_params(Type::LowPass, fs, f0, 0.0);
+        assert_eq!(coeffs.unwrap_err(), Errors::QTooSmall);
+
+        let coeffs = Coefficients::<f64>::try_from_params(Type::LowPass, fs, f0, f64::NAN);
+        assert_eq!(coeffs.unwrap_err(), Errors::QTooSmall);
+
+        let coeffs = Coefficients::<f64>::try_from_params(Type::LowPass, fs, f0, -1.0);
+        assert_eq!(coeffs.unwrap_err(), Errors::NegativeQ);
+
+        // The single pole filters do not use Q
+        let coeffs = Coefficients::<f64>::try_from_params(Type::SinglePoleLowPass, fs, f0, 0.0);
+        assert!(coeffs.is_ok());
+
+        let coeffs =
+            Coefficients::<f64>::try_from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(coeffs.a1.is_finite() && coeffs.a2.is_finite() && coeffs.b0.is_finite());
+    }
+
+    #[test]
+    fn test_latency_samples_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        assert_eq!(DirectForm1::<f32>::new(coeffs).latency_samples(), 0);
+        assert_eq!(
+            DirectForm2Transposed::<f32>::new(coeffs).latency_samples(),
+            0
+        );
+
+        let saturating =
+            SaturatingBiquad::<_, f32>::new(DirectForm1::<f32>::new(coeffs), 1.0).unwrap();
+        assert_eq!(saturating.latency_samples(), 0);
+    }
+
+    #[test]
+    fn test_latency_samples_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        assert_eq!(DirectForm1::<f64>::new(coeffs).latency_samples(), 0);
+        assert_eq!(
+            DirectForm2Transposed::<f64>::new(coeffs).latency_samples(),
+            0
+        );
+
+        let saturating =
+            SaturatingBiquad::<_, f64>::new(DirectForm1::<f64>::new(coeffs), 1.0).unwrap();
+        assert_eq!(saturating.latency_samples(), 0);
+    }
+
+    #[test]
+    fn test_analog_round_trip_f64() {
+        let fs = 48.khz();
+        let w0 = 2.0 * core::f64::consts::PI * 1000.0;
+        let q = 2.0;
+
+        let b = [0.0, w0 / q, 0.0];
+        let a = [1.0, w0 / q, w0 * w0];
+        let (b_rec, a_rec) = Coefficients::<f64>::from_analog(b, a, fs).to_analog(fs);
+
+        for i in 0..3 {
+            assert!((b_rec[i] - b[i]).abs() <= 1e-6 * (1.0 + b[i].abs()));
+            assert!((a_rec[i] - a[i]).abs() <= 1e-6 * (1.0 + a[i].abs()));
+        }
+
+        // A cookbook design corresponds to the analog prototype at the warped frequency
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), q).unwrap();
+        let (b, a) = coeffs.to_analog(fs);
+        let warped = 2.0 * fs.hz() * libm::tan(core::f64::consts::PI * 1000.0 / fs.hz());
+
+        assert!((a[1] - warped / q).abs() < 1e-6 * warped);
+        assert!((a[2] - warped * warped).abs() < 1e-6 * warped * warped);
+        assert!((b[2] - warped * warped).abs() < 1e-6 * warped * warped);
+    }
+
+    #[test]
+    fn test_step_overshoot_f32() {
+        let fs = 1.khz();
+        let f0 = 10.hz();
+
+        let butterworth =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        let overshoot = butterworth.step_overshoot();
+        assert!((overshoot - 4.3).abs() < 0.1, "{}", overshoot);
+
+        let critical = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 0.5).unwrap();
+        assert_eq!(critical.step_overshoot(), 0.0);
+    }
+
+    #[test]
+    fn test_step_overshoot_f64() {
+        let fs = 1.khz();
+        let f0 = 10.hz();
+
+        let butterworth =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        let overshoot = butterworth.step_overshoot();
+        assert!((overshoot - 4.3).abs() < 0.1, "{}", overshoot);
+
+        let critical = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 0.5).unwrap();
+        assert_eq!(critical.step_overshoot(), 0.0);
+    }
+
+    #[test]
+    fn test_cascade_coefficients_iter_mut_f32() {
+        let fs = 1.khz();
+        let first = Coefficients::<f32>::from_params(Type::LowPass, fs, 10.hz(), 0.54).unwrap();
+        let second = Coefficients::<f32>::from_params(Type::LowPass, fs, 10.hz(), 1.31).unwrap();
+
+        let mut reference = Cascade::new([
+            DirectForm2Transposed::<f32>::new(first),
+            DirectForm2Transposed::<f32>::new(second),
+        ]);
+        let mut scaled = reference;
+
+        // Scale the gain of every section by 2, the combined gain is scaled by 4
+        for coeffs in scaled.coefficients_iter_mut() {
+            coeffs.b0 *= 2.0;
+            coeffs.b1 *= 2.0;
+            coeffs.b2 *= 2.0;
+        }
+
+        for _ in 0..200 {
+            let expected = 4.0 * reference.run(1.0);
+            let output = scaled.run(1.0);
+            assert!((output - expected).abs() <= 1e-5 * expected.abs());
+        }
+    }
+
+    #[test]
+    fn test_cascade_coefficients_iter_mut_f64() {
+        let fs = 1.khz();
+        let first = Coefficients::<f64>::from_params(Type::LowPass, fs, 10.hz(), 0.54).unwrap();
+        let second = Coefficients::<f64>::from_params(Type::LowPass, fs, 10.hz(), 1.31).unwrap();
+
+        let mut reference = Cascade::new([
+            DirectForm2Transposed::<f64>::new(first),
+            DirectForm2Transposed::<f64>::new(second),
+        ]);
+        let mut scaled = reference;
+
+        // Scale the gain of every section by 2, the combined gain is scaled by 4
+        for coeffs in scaled.coefficients_iter_mut() {
+            coeffs.b0 *= 2.0;
+            coeffs.b1 *= 2.0;
+            coeffs.b2 *= 2.0;
+        }
+
+        for _ in 0..200 {
+            let expected = 4.0 * reference.run(1.0);
+            let output = scaled.run(1.0);
+            assert!((output - expected).abs() <= 1e-5 * expected.abs());
+        }
+    }
+
+    #[test]
+    fn test_high_shelf_maxflat_f32() {
+        let fs = 48.khz();
+
+        for &gain in &[-12.0, 6.0, 18.0] {
+            let coeffs = Coefficients::<f32>::high_shelf_maxflat(fs, 2.khz(), gain).unwrap();
+
+            let mut previous = 0.0;
+            for i in 0..=256 {
+                let omega = core::f32::consts::PI * i as f32 / 256.0;
+                let (re, im) = coeffs.response_at(omega);
+                let db = 10.0 * libm::log10f(re * re + im * im);
+
+                // Monotonic towards the target gain, without overshoot
+                if i > 0 {
+                    assert!((db - previous) * gain.signum() >= -1e-4);
+                }
+                assert!(db.abs() <= gain.abs() + 1e-3);
+                previous = db;
+            }
+            assert!((previous - gain).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_high_shelf_maxflat_f64() {
+        let fs = 48.khz();
+
+        for &gain in &[-12.0, 6.0, 18.0] {
+            let coeffs = Coefficients::<f64>::high_shelf_maxflat(fs, 2.khz(), gain).unwrap();
+
+            let mut previous = 0.0;
+            for i in 0..=256 {
+                let omega = core::f64::consts::PI * i as f64 / 256.0;
+                let (re, im) = coeffs.response_at(omega);
+                let db = 10.0 * libm::log10(re * re + im * im);
+
+                // Monotonic towards the target gain, without overshoot
+                if i > 0 {
+                    assert!((db - previous) * gain.signum() >= -1e-4);
+                }
+                assert!(db.abs() <= gain.abs() + 1e-3);
+                previous = db;
+            }
+            assert!((previous - gain).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_impulse_energy_f32() {
+        let fs = 1.khz();
+
+        // An allpass preserves energy
+        let allpass = Coefficients::<f32>::from_params(Type::AllPass, fs, 100.hz(), 1.0).unwrap();
+        assert!((allpass.impulse_energy(1000) - 1.0).abs() < 1e-4);
+
+        // By Parseval, the energy equals the mean of |H|^2 over the frequency axis
+        let lowpass =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 100.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let points = 4096;
+        let parseval = (0..points)
+            .map(|i| {
+                let (re, im) =
+                    lowpass.response_at(core::f32::consts::PI * (i as f32 + 0.5) / points as f32);
+                re * re + im * im
+            })
+            .sum::<f32>()
+            / points as f32;
+
+        let energy = lowpass.impulse_energy(1000);
+        assert!(energy.is_finite() && energy > 0.0 && energy < 1.0);
+        assert!((energy - parseval).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_impulse_energy_f64() {
+        let fs = 1.khz();
+
+        // An allpass preserves energy
+        let allpass = Coefficients::<f64>::from_params(Type::AllPass, fs, 100.hz(), 1.0).unwrap();
+        assert!((allpass.impulse_energy(1000) - 1.0).abs() < 1e-4);
+
+        // By Parseval, the energy equals the mean of |H|^2 over the frequency axis
+        let lowpass =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 100.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let points = 4096;
+        let parseval = (0..points)
+            .map(|i| {
+                let (re, im) =
+                    lowpass.response_at(core::f64::consts::PI * (i as f64 + 0.5) / points as f64);
+                re * re + im * im
+            })
+            .sum::<f64>()
+            / points as f64;
+
+        let energy = lowpass.impulse_energy(1000);
+        assert!(energy.is_finite() && energy > 0.0 && energy < 1.0);
+        assert!((energy - parseval).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_run_wet_dry_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+
+        for i in 0..32 {
+            let input = i as f32 * 0.1;
+            let (wet, dry) = biquad.run_wet_dry(input);
+            assert_eq!(dry, input);
+            assert_eq!(wet, reference.run(input));
+        }
+    }
+
+    #[test]
+    fn test_run_wet_dry_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+
+        for i in 0..32 {
+            let input = i as f64 * 0.1;
+            let (wet, dry) = biquad.run_wet_dry(input);
+            assert_eq!(dry, input);
+            assert_eq!(wet, reference.run(input));
+        }
+    }
+
+    #[test]
+    fn test_try_combine_f32() {
+        let fs = 1.khz();
+        let gain = Coefficients::<f32> {
+            a1: 0.0,
+            a2: 0.0,
+            b0: 0.5,
+            b1: 0.0,
+            b2: 0.0,
+        };
+        let lowpass =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let combined = gain.try_combine(&lowpass).unwrap();
+        let mut single = DirectForm1::<f32>::new(combined);
+        let mut cascade = Cascade::new([
+            DirectForm1::<f32>::new(gain),
+            DirectForm1::<f32>::new(lowpass),
+        ]);
+
+        for i in 0..100 {
+            let input = if i % 10 == 0 { 1.0 } else { 0.0 };
+            let expected = cascade.run(input);
+            assert!((single.run(input) - expected).abs() <= 1e-6);
+        }
+
+        // Two first order sections combine, two second order sections do not
+        let single_pole =
+            Coefficients::<f32>::from_params(Type::SinglePoleLowPass, fs, 10.hz(), 0.0).unwrap();
+        assert!(single_pole.try_combine(&single_pole).is_some());
+        assert!(lowpass.try_combine(&lowpass).is_none());
+    }
+
+    #[test]
+    fn test_try_combine_f64() {
+        let fs = 1.khz();
+        let gain = Coefficients::<f64> {
+            a1: 0.0,
+            a2: 0.0,
+            b0: 0.5,
+            b1: 0.0,
+            b2: 0.0,
+        };
+        let lowpass =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let combined = gain.try_combine(&lowpass).unwrap();
+        let mut single = DirectForm1::<f64>::new(combined);
+        let mut cascade = Cascade::new([
+            DirectForm1::<f64>::new(gain),
+            DirectForm1::<f64>::new(lowpass),
+        ]);
+
+        for i in 0..100 {
+            let input = if i % 10 == 0 { 1.0 } else { 0.0 };
+            let expected = cascade.run(input);
+            assert!((single.run(input) - expected).abs() <= 1e-6);
+        }
+
+        // Two first order sections combine, two second order sections do not
+        let single_pole =
+            Coefficients::<f64>::from_params(Type::SinglePoleLowPass, fs, 10.hz(), 0.0).unwrap();
+        assert!(single_pole.try_combine(&single_pole).is_some());
+        assert!(lowpass.try_combine(&lowpass).is_none());
+    }
+
+    #[test]
+    fn test_type_through_f32() {
+        // Through ignores the other parameters, even invalid ones
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::Through, 10.hz(), 1.khz(), -1.0).unwrap();
+        assert!(coeffs.is_identity(0.0));
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::Through, 1.khz(), 10.hz(), 0.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+        for i in 0..16 {
+            let input = i as f32 - 8.0;
+            assert_eq!(biquad.run(input), input);
+        }
+    }
+
+    #[test]
+    fn test_type_through_f64() {
+        // Through ignores the other parameters, even invalid ones
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::Through, 10.hz(), 1.khz(), -1.0).unwrap();
+        assert!(coeffs.is_identity(0.0));
+
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::Through, 1.khz(), 10.hz(), 0.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f64>::new(coeffs);
+        for i in 0..16 {
+            let input = i as f64 - 8.0;
+            assert_eq!(biquad.run(input), input);
+        }
+    }
+
+    #[test]
+    fn test_loudness_compensation_f32() {
+        let fs = 48.khz();
+
+        let gain_db = |playback: f32, f: f32| {
+            let cascade =
+                Cascade::<DirectForm1<f32>, 2>::loudness_compensation(fs, playback).unwrap();
+            let omega = 2.0 * core::f32::consts::PI * f / fs.hz();
+            cascade
+                .stages()
+                .iter()
+                .map(|stage| {
+                    let (re, im) = stage.coeffs.response_at(omega);
+                    10.0 * libm::log10f(re * re + im * im)
+                })
+                .sum::<f32>()
+        };
+
+        // Lower playback levels need more bass boost
+        assert!(gain_db(40.0, 50.0) > gain_db(60.0, 50.0));
+        assert!(gain_db(60.0, 50.0) > gain_db(80.0, 50.0));
+        assert!(gain_db(40.0, 50.0) > 6.0);
+
+        // No compensation at the reference level
+        assert!(gain_db(83.0, 50.0).abs() < 1e-3);
+        assert!(gain_db(83.0, 10_000.0).abs() < 1e-3);
+
+        // The midrange is left alone
+        assert!(gain_db(40.0, 1_000.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_loudness_compensation_f64() {
+        let fs = 48.khz();
+
+        let gain_db = |playback: f64, f: f64| {
+            let cascade =
+                Cascade::<DirectForm1<f64>, 2>::loudness_compensation(fs, playback).unwrap();
+            let omega = 2.0 * core::f64::consts::PI * f / fs.hz();
+            cascade
+                .stages()
+                .iter()
+                .map(|stage| {
+                    let (re, im) = stage.coeffs.response_at(omega);
+                    10.0 * libm::log10(re * re + im * im)
+                })
+                .sum::<f64>()
+        };
+
+        // Lower playback levels need more bass boost
+        assert!(gain_db(40.0, 50.0) > gain_db(60.0, 50.0));
+        assert!(gain_db(60.0, 50.0) > gain_db(80.0, 50.0));
+        assert!(gain_db(40.0, 50.0) > 6.0);
+
+        // No compensation at the reference level
+        assert!(gain_db(83.0, 50.0).abs() < 1e-3);
+        assert!(gain_db(83.0, 10_000.0).abs() < 1e-3);
+
+        // The midrange is left alone
+        assert!(gain_db(40.0, 1_000.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_band_pass_peak_db_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 2.khz();
+        let omega = 2.0 * core::f32::consts::PI * f0.hz() / fs.hz();
+
+        for &q in &[0.3, Q_BUTTERWORTH_F32, 2.0, 10.0] {
+            for &peak_db in &[-6.0, 0.0, 12.0] {
+                let coeffs = Coefficients::<f32>::band_pass_peak_db(fs, f0, q, peak_db).unwrap();
+                let (re, im) = coeffs.response_at(omega);
+                let magnitude_db = 10.0 * libm::log10f(re * re + im * im);
+
+                assert!((magnitude_db - peak_db).abs() < 1e-3);
+            }
+        }
+
+        assert!(matches!(
+            Coefficients::<f32>::band_pass_peak_db(fs, 30.khz(), 1.0, 0.0),
+            Err(Errors::OutsideNyquist)
+        ));
+    }
+
+    #[test]
+    fn test_band_pass_peak_db_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 2.khz();
+        let omega = 2.0 * core::f64::consts::PI * f0.hz() / fs.hz();
+
+        for &q in &[0.3, Q_BUTTERWORTH_F64, 2.0, 10.0] {
+            for &peak_db in &[-6.0, 0.0, 12.0] {
+                let coeffs = Coefficients::<f64>::band_pass_peak_db(fs, f0, q, peak_db).unwrap();
+                let (re, im) = coeffs.response_at(omega);
+                let magnitude_db = 10.0 * libm::log10(re * re + im * im);
+
+                assert!((magnitude_db - peak_db).abs() < 1e-3);
+            }
+        }
+
+        assert!(matches!(
+            Coefficients::<f64>::band_pass_peak_db(fs, 30.khz(), 1.0, 0.0),
+            Err(Errors::OutsideNyquist)
+        ));
+    }
+
+    #[test]
+    fn test_run_block_enveloped_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 3.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let input: [f32; 64] =
+            core::array::from_fn(|i| if i % 7 == 0 { 1.0 } else { -0.25 });
+        let env: [f32; 64] = core::array::from_fn(|i| i as f32 / 63.0);
+
+        let mut fused = DirectForm1::<f32>::new(coeffs);
+        let mut buf = input;
+        fused.run_block_enveloped(&mut buf, &env);
+
+        let mut separate = DirectForm1::<f32>::new(coeffs);
+        for ((&x, &gain), &y) in input.iter().zip(&env).zip(&buf) {
+            assert_eq!(separate.run(x) * gain, y);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_run_block_enveloped_length_mismatch_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 3.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+
+        biquad.run_block_enveloped(&mut [0.0; 4], &[1.0; 3]);
+    }
+
+    #[test]
+    fn test_run_block_enveloped_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 3.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let input: [f64; 64] =
+            core::array::from_fn(|i| if i % 7 == 0 { 1.0 } else { -0.25 });
+        let env: [f64; 64] = core::array::from_fn(|i| i as f64 / 63.0);
+
+        let mut fused = DirectForm1::<f64>::new(coeffs);
+        let mut buf = input;
+        fused.run_block_enveloped(&mut buf, &env);
+
+        let mut separate = DirectForm1::<f64>::new(coeffs);
+        for ((&x, &gain), &y) in input.iter().zip(&env).zip(&buf) {
+            assert_eq!(separate.run(x) * gain, y);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_run_block_enveloped_length_mismatch_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 3.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let mut biquad = DirectForm2Transposed::<f64>::new(coeffs);
+
+        biquad.run_block_enveloped(&mut [0.0; 4], &[1.0; 3]);
+    }
+
+    #[test]
+    fn test_canonical_key_f32() {
+        let design = |f0: f32| {
+            Coefficients::<f32>::from_params(Type::PeakingEQ(6.0), 48.khz(), f0.hz(), 2.0).unwrap()
+        };
+
+        assert_eq!(
+            design(1000.0).canonical_key(),
+            design(1000.0).canonical_key()
+        );
+        assert_ne!(
+            design(1000.0).canonical_key(),
+            design(1001.0).canonical_key()
+        );
+
+        // Signed zeros compare equal, so they share a key
+        let positive =
+            Coefficients::<f32>::from_params(Type::Through, 48.khz(), 1.khz(), 1.0).unwrap();
+        let negative = Coefficients {
+            b1: -0.0,
+            ..positive
+        };
+        assert_eq!(positive.canonical_key(), negative.canonical_key());
+    }
+
+    #[test]
+    fn test_canonical_key_f64() {
+        let design = |f0: f64| {
+            Coefficients::<f64>::from_params(Type::PeakingEQ(6.0), 48.khz(), f0.hz(), 2.0).unwrap()
+        };
+
+        assert_eq!(
+            design(1000.0).canonical_key(),
+            design(1000.0).canonical_key()
+        );
+        assert_ne!(
+            design(1000.0).canonical_key(),
+            design(1001.0).canonical_key()
+        );
+
+        // Signed zeros compare equal, so they share a key
+        let positive =
+            Coefficients::<f64>::from_params(Type::Through, 48.khz(), 1.khz(), 1.0).unwrap();
+        let negative = Coefficients {
+            b1: -0.0,
+            ..positive
+        };
+        assert_eq!(positive.canonical_key(), negative.canonical_key());
+    }
+
+    #[test]
+    fn test_approx_eq_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::HighPass, 48.khz(), 200.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let perturbed = Coefficients {
+            a1: coeffs.a1 + 1e-4,
+            b2: coeffs.b2 - 1e-4,
+            ..coeffs
+        };
+
+        assert!(coeffs.approx_eq(&coeffs, 0.0));
+        assert!(coeffs.approx_eq(&perturbed, 1e-3));
+        assert!(perturbed.approx_eq(&coeffs, 1e-3));
+        assert!(!coeffs.approx_eq(&perturbed, 1e-5));
+    }
+
+    #[test]
+    fn test_approx_eq_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::HighPass, 48.khz(), 200.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let perturbed = Coefficients {
+            a1: coeffs.a1 + 1e-4,
+            b2: coeffs.b2 - 1e-4,
+            ..coeffs
+        };
+
+        assert!(coeffs.approx_eq(&coeffs, 0.0));
+        assert!(coeffs.approx_eq(&perturbed, 1e-3));
+        assert!(perturbed.approx_eq(&coeffs, 1e-3));
+        assert!(!coeffs.approx_eq(&perturbed, 1e-5));
+    }
+
+    #[test]
+    fn test_debug_biquad_history_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 2.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let mut biquad = DebugBiquad::<_, f32, 4>::new(DirectForm1::<f32>::new(coeffs));
+
+        assert!(biquad.history().is_empty());
+
+        let inputs = [1.0, -0.5, 0.25, 0.75, -1.0, 0.5];
+        let outputs = inputs.map(|x| reference.run(x));
+
+        for (i, &x) in inputs.iter().enumerate() {
+            assert_eq!(biquad.run(x), outputs[i]);
+
+            let len = (i + 1).min(4);
+            let first = i + 1 - len;
+            assert_eq!(biquad.history().len(), len);
+            for (k, &pair) in biquad.history().iter().enumerate() {
+                assert_eq!(pair, (inputs[first + k], outputs[first + k]));
+            }
+        }
+
+        // Recording continues in order after the wrapped history has been rotated
+        let more = [0.125, 0.25].map(|x| (x, reference.run(x)));
+        for &(x, _) in &more {
+            biquad.run(x);
+        }
+        assert_eq!(
+            biquad.history(),
+            &[
+                (inputs[4], outputs[4]),
+                (inputs[5], outputs[5]),
+                more[0],
+                more[1]
+            ]
+        );
+
+        biquad.clear_history();
+        assert!(biquad.history().is_empty());
+        biquad.run(1.0);
+        assert_eq!(biquad.history(), &[(1.0, reference.run(1.0))]);
+    }
+
+    #[test]
+    fn test_debug_biquad_history_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 2.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let mut biquad = DebugBiquad::<_, f64, 4>::new(DirectForm1::<f64>::new(coeffs));
+
+        assert!(biquad.history().is_empty());
+
+        let inputs = [1.0, -0.5, 0.25, 0.75, -1.0, 0.5];
+        let outputs = inputs.map(|x| reference.run(x));
+
+        for (i, &x) in inputs.iter().enumerate() {
+            assert_eq!(biquad.run(x), outputs[i]);
+
+            let len = (i + 1).min(4);
+            let first = i + 1 - len;
+            assert_eq!(biquad.history().len(), len);
+            for (k, &pair) in biquad.history().iter().enumerate() {
+                assert_eq!(pair, (inputs[first + k], outputs[first + k]));
+            }
+        }
+
+        // Recording continues in order after the wrapped history has been rotated
+        let more = [0.125, 0.25].map(|x| (x, reference.run(x)));
+        for &(x, _) in &more {
+            biquad.run(x);
+        }
+        assert_eq!(
+            biquad.history(),
+            &[
+                (inputs[4], outputs[4]),
+                (inputs[5], outputs[5]),
+                more[0],
+                more[1]
+            ]
+        );
+
+        biquad.clear_history();
+        assert!(biquad.history().is_empty());
+        biquad.run(1.0);
+        assert_eq!(biquad.history(), &[(1.0, reference.run(1.0))]);
+    }
+
+    #[test]
+    fn test_power_complementary_pair_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let (low, high) = Coefficients::<f32>::power_complementary_pair(fs, 2.khz()).unwrap();
+
+        for i in 1..200 {
+            let omega = core::f32::consts::PI * i as f32 / 200.0;
+            let (lr, li) = low.response_at(omega);
+            let (hr, hi) = high.response_at(omega);
+            let power = lr * lr + li * li + hr * hr + hi * hi;
+
+            assert!((power - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_power_complementary_pair_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let (low, high) = Coefficients::<f64>::power_complementary_pair(fs, 2.khz()).unwrap();
+
+        for i in 1..200 {
+            let omega = core::f64::consts::PI * i as f64 / 200.0;
+            let (lr, li) = low.response_at(omega);
+            let (hr, hi) = high.response_at(omega);
+            let power = lr * lr + li * li + hr * hr + hi * hi;
+
+            assert!((power - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_clamp_to_nyquist_f32() {
+        let fs: Hertz<f32> = 48.khz();
+
+        let above: Hertz<f32> = 30.khz();
+        let clamped = above.clamp_to_nyquist(fs);
+        assert!(clamped.hz() < 24_000.0);
+        assert!(clamped.hz() > 23_999.0);
+        assert!(
+            Coefficients::<f32>::from_params(Type::LowPass, fs, clamped, Q_BUTTERWORTH_F32).is_ok()
+        );
+
+        let valid: Hertz<f32> = 1.khz();
+        assert_eq!(valid.clamp_to_nyquist(fs), valid);
+    }
+
+    #[test]
+    fn test_clamp_to_nyquist_f64() {
+        let fs: Hertz<f64> = 48.khz();
+
+        let above: Hertz<f64> = 30.khz();
+        let clamped = above.clamp_to_nyquist(fs);
+        assert!(clamped.hz() < 24_000.0);
+        assert!(clamped.hz() > 23_999.0);
+        assert!(
+            Coefficients::<f64>::from_params(Type::LowPass, fs, clamped, Q_BUTTERWORTH_F64).is_ok()
+        );
+
+        let valid: Hertz<f64> = 1.khz();
+        assert_eq!(valid.clamp_to_nyquist(fs), valid);
+    }
+
+    #[test]
+    fn test_apply_to_sinusoid_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f: Hertz<f32> = 3.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(-6.0), fs, 2.khz(), 1.5).unwrap();
+
+        let (amplitude, phase) = (0.5, 0.3);
+        let (out_amplitude, out_phase) = coeffs.apply_to_sinusoid(fs, f, amplitude, phase);
+
+        let omega = 2.0 * core::f32::consts::PI * f.hz() / fs.hz();
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+        for n in 0..4000 {
+            let t = n as f32 * omega;
+            let output = biquad.run(amplitude * libm::sinf(t + phase));
+
+            // Compare once the transient has decayed
+            if n >= 2000 {
+                let expected = out_amplitude * libm::sinf(t + out_phase);
+                assert!((output - expected).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_to_sinusoid_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f: Hertz<f64> = 3.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(-6.0), fs, 2.khz(), 1.5).unwrap();
+
+        let (amplitude, phase) = (0.5, 0.3);
+        let (out_amplitude, out_phase) = coeffs.apply_to_sinusoid(fs, f, amplitude, phase);
+
+        let omega = 2.0 * core::f64::consts::PI * f.hz() / fs.hz();
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+        for n in 0..4000 {
+            let t = n as f64 * omega;
+            let output = biquad.run(amplitude * libm::sin(t + phase));
+
+            // Compare once the transient has decayed
+            if n >= 2000 {
+                let expected = out_amplitude * libm::sin(t + out_phase);
+                assert!((output - expected).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_pole_lowpass_matched_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let power = |coeffs: &Coefficients<f32>, f: f32| {
+            let (re, im) = coeffs.response_at(2.0 * core::f32::consts::PI * f / fs.hz());
+            re * re + im * im
+        };
+
+        for &f0 in &[100.0, 1_000.0, 10_000.0, 20_000.0, 23_000.0] {
+            let coeffs = Coefficients::<f32>::single_pole_lowpass_matched(fs, f0.hz()).unwrap();
+
+            // The half power point lies within 0.1% of f0
+            assert!(power(&coeffs, f0 * 0.999) > 0.5);
+            assert!(power(&coeffs, f0 * 1.001) < 0.5);
+        }
+
+        // The approximation misses the cutoff near Nyquist
+        let approx = Coefficients::<f32>::from_params(
+            Type::SinglePoleLowPassApprox,
+            fs,
+            20.khz(),
+            Q_BUTTERWORTH_F32,
+        )
+        .unwrap();
+        assert!(power(&approx, 20_000.0 * 0.999) < 0.5);
+
+        assert_eq!(
+            Coefficients::<f32>::single_pole_lowpass_matched(fs, 30.khz()),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[test]
+    fn test_single_pole_lowpass_matched_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let power = |coeffs: &Coefficients<f64>, f: f64| {
+            let (re, im) = coeffs.response_at(2.0 * core::f64::consts::PI * f / fs.hz());
+            re * re + im * im
+        };
+
+        for &f0 in &[100.0, 1_000.0, 10_000.0, 20_000.0, 23_000.0] {
+            let coeffs = Coefficients::<f64>::single_pole_lowpass_matched(fs, f0.hz()).unwrap();
+
+            // The half power point lies within 0.1% of f0
+            assert!(power(&coeffs, f0 * 0.999) > 0.5);
+            assert!(power(&coeffs, f0 * 1.001) < 0.5);
+        }
+
+        // The approximation misses the cutoff near Nyquist
+        let approx = Coefficients::<f64>::from_params(
+            Type::SinglePoleLowPassApprox,
+            fs,
+            20.khz(),
+            Q_BUTTERWORTH_F64,
+        )
+        .unwrap();
+        assert!(power(&approx, 20_000.0 * 0.999) < 0.5);
+
+        assert_eq!(
+            Coefficients::<f64>::single_pole_lowpass_matched(fs, 30.khz()),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[test]
+    fn test_cascade_split_at_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let stage = |f0: f32| {
+            DirectForm2Transposed::<f32>::new(
+                Coefficients::<f32>::from_params(Type::PeakingEQ(3.0), fs, f0.hz(), 1.0).unwrap(),
+            )
+        };
+        let mut whole = Cascade::new([
+            stage(100.0),
+            stage(1_000.0),
+            stage(5_000.0),
+            stage(10_000.0),
+        ]);
+        let mut split = whole;
+
+        for n in 0..64 {
+            let input = if n % 5 == 0 { 1.0 } else { -0.3 };
+            let expected = whole.run(input);
+
+            let (first, rest) = split.split_at(1);
+            let tap = first.iter_mut().fold(input, |x, stage| stage.run(x));
+            let output = rest.iter_mut().fold(tap, |x, stage| stage.run(x));
+
+            assert_eq!(output, expected);
+        }
+
+        let (first, rest) = split.split_at(4);
+        assert_eq!((first.len(), rest.len()), (4, 0));
+    }
+
+    #[test]
+    fn test_cascade_split_at_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let stage = |f0: f64| {
+            DirectForm2Transposed::<f64>::new(
+                Coefficients::<f64>::from_params(Type::PeakingEQ(3.0), fs, f0.hz(), 1.0).unwrap(),
+            )
+        };
+        let mut whole = Cascade::new([
+            stage(100.0),
+            stage(1_000.0),
+            stage(5_000.0),
+            stage(10_000.0),
+        ]);
+        let mut split = whole;
+
+        for n in 0..64 {
+            let input = if n % 5 == 0 { 1.0 } else { -0.3 };
+            let expected = whole.run(input);
+
+            let (first, rest) = split.split_at(1);
+            let tap = first.iter_mut().fold(input, |x, stage| stage.run(x));
+            let output = rest.iter_mut().fold(tap, |x, stage| stage.run(x));
+
+            assert_eq!(output, expected);
+        }
+
+        let (first, rest) = split.split_at(4);
+        assert_eq!((first.len(), rest.len()), (4, 0));
+    }
+
+    #[test]
+    fn test_from_params_unchecked_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let filters = [
+            Type::SinglePoleLowPassApprox,
+            Type::SinglePoleLowPass,
+            Type::LowPass,
+            Type::HighPass,
+            Type::BandPass,
+            Type::Notch,
+            Type::AllPass,
+            Type::LowShelf(4.0),
+            Type::HighShelf(-4.0),
+            Type::PeakingEQ(6.0),
+            Type::Through,
+        ];
+
+        for &filter in &filters {
+            for &f0 in &[20.0, 1_000.0, 23_000.0] {
+                let checked =
+                    Coefficients::<f32>::from_params_fast(filter, fs, f0.hz(), 0.9).unwrap();
+                let unchecked =
+                    Coefficients::<f32>::from_params_unchecked(filter, fs, f0.hz(), 0.9);
+
+                assert_eq!(checked.canonical_key(), unchecked.canonical_key());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_params_unchecked_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let filters = [
+            Type::SinglePoleLowPassApprox,
+            Type::SinglePoleLowPass,
+            Type::LowPass,
+            Type::HighPass,
+            Type::BandPass,
+            Type::Notch,
+            Type::AllPass,
+            Type::LowShelf(4.0),
+            Type::HighShelf(-4.0),
+            Type::PeakingEQ(6.0),
+            Type::Through,
+        ];
+
+        for &filter in &filters {
+            for &f0 in &[20.0, 1_000.0, 23_000.0] {
+                let checked = Coefficients::<f64>::from_params(filter, fs, f0.hz(), 0.9).unwrap();
+                let unchecked =
+                    Coefficients::<f64>::from_params_unchecked(filter, fs, f0.hz(), 0.9);
+
+                assert_eq!(checked.canonical_key(), unchecked.canonical_key());
+            }
+        }
+    }
+
+    #[test]
+    fn test_peaking_gain_at_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let f_measure: Hertz<f32> = 1.5.khz();
+        let omega = 2.0 * core::f32::consts::PI * f_measure.hz() / fs.hz();
+
+        for &target in &[-9.0, -1.5, 0.0, 2.0, 6.0] {
+            let coeffs =
+                Coefficients::<f32>::peaking_gain_at(fs, f0, 2.0, f_measure, target).unwrap();
+            let (re, im) = coeffs.response_at(omega);
+            let gain = 10.0 * libm::log10f(re * re + im * im);
+
+            assert!((gain - target).abs() < 1e-2);
+        }
+
+        let above: Hertz<f32> = 30.khz();
+        assert!(matches!(
+            Coefficients::<f32>::peaking_gain_at(fs, f0, 2.0, above, 3.0),
+            Err(Errors::OutsideNyquist)
+        ));
+
+        // The gain at Nyquist is 0 dB for any center gain, and a narrow band lifts a frequency a
+        // decade away by only about half the center gain
+        let nyquist: Hertz<f32> = 24.khz();
+        let far: Hertz<f32> = 10.khz();
+        let unreachable = [(nyquist, 3.0), (nyquist, -3.0), (far, 600.0), (far, -600.0)];
+        for &(f_measure, target) in &unreachable {
+            assert_eq!(
+                Coefficients::<f32>::peaking_gain_at(fs, f0, 10.0, f_measure, target),
+                Err(Errors::GainUnreachable)
+            );
+        }
+    }
+
+    #[test]
+    fn test_peaking_gain_at_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let f_measure: Hertz<f64> = 1.5.khz();
+        let omega = 2.0 * core::f64::consts::PI * f_measure.hz() / fs.hz();
+
+        for &target in &[-9.0, -1.5, 0.0, 2.0, 6.0] {
+            let coeffs =
+                Coefficients::<f64>::peaking_gain_at(fs, f0, 2.0, f_measure, target).unwrap();
+            let (re, im) = coeffs.response_at(omega);
+            let gain = 10.0 * libm::log10(re * re + im * im);
+
+            assert!((gain - target).abs() < 1e-2);
+        }
+
+        let above: Hertz<f64> = 30.khz();
+        assert!(matches!(
+            Coefficients::<f64>::peaking_gain_at(fs, f0, 2.0, above, 3.0),
+            Err(Errors::OutsideNyquist)
+        ));
+
+        // The gain at Nyquist is 0 dB for any center gain, and a narrow band lifts a frequency a
+        // decade away by only about half the center gain
+        let nyquist: Hertz<f64> = 24.khz();
+        let far: Hertz<f64> = 10.khz();
+        let unreachable = [(nyquist, 3.0), (nyquist, -3.0), (far, 600.0), (far, -600.0)];
+        for &(f_measure, target) in &unreachable {
+            assert_eq!(
+                Coefficients::<f64>::peaking_gain_at(fs, f0, 10.0, f_measure, target),
+                Err(Errors::GainUnreachable)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_params_double_precision_f32() {
+        let reference =
+            Coefficients::<f64>::from_params(Type::LowPass, 192.khz(), 20.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let error = |coeffs: Coefficients<f32>| {
+            (coeffs.a1 as f64 - reference.a1).abs()
+                + (coeffs.a2 as f64 - reference.a2).abs()
+                + (coeffs.b0 as f64 - reference.b0).abs()
+                + (coeffs.b1 as f64 - reference.b1).abs()
+                + (coeffs.b2 as f64 - reference.b2).abs()
+        };
+
+        let precise =
+            Coefficients::<f32>::from_params(Type::LowPass, 192.khz(), 20.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let fast = Coefficients::<f32>::from_params_fast(
+            Type::LowPass,
+            192.khz(),
+            20.hz(),
+            Q_BUTTERWORTH_F32,
+        )
+        .unwrap();
+
+        assert!(error(precise) < error(fast));
+
+        // Only the final rounding to f32 remains
+        assert_eq!(precise.a1, reference.a1 as f32);
+        assert_eq!(precise.a2, reference.a2 as f32);
+        assert_eq!(precise.b0, reference.b0 as f32);
+    }
+
+    #[test]
+    fn test_cascade_bytes_round_trip_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let stage = |filter, f0: f32| {
+            DirectForm2Transposed::<f32>::new(
+                Coefficients::<f32>::from_params(filter, fs, f0.hz(), 0.8).unwrap(),
+            )
+        };
+        let cascade = Cascade::new([
+            stage(Type::HighPass, 30.0),
+            stage(Type::PeakingEQ(-3.0), 800.0),
+            stage(Type::HighShelf(2.0), 8_000.0),
+        ]);
+
+        let mut bytes = [0u8; Cascade::<DirectForm2Transposed<f32>, 3>::ENCODED_LEN];
+        assert_eq!(cascade.write_bytes(&mut bytes), 4 + 3 * 5 * 4);
+        assert_eq!(&bytes[..4], &[1, 4, 3, 0]);
+
+        let decoded = Cascade::<DirectForm2Transposed<f32>, 3>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.stages().len(), 3);
+        for (a, b) in cascade.stages().iter().zip(decoded.stages()) {
+            assert_eq!(
+                a.coefficients().canonical_key(),
+                b.coefficients().canonical_key()
+            );
+        }
+
+        // Mismatching stage counts, float sizes and truncated data are rejected
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f32>, 2>::from_bytes(&bytes).unwrap_err(),
+            Errors::InvalidEncoding
+        );
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f32>, 3>::from_bytes(&bytes[..bytes.len() - 1])
+                .unwrap_err(),
+            Errors::InvalidEncoding
+        );
+        let mut wrong_version = bytes;
+        wrong_version[0] = 2;
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f32>, 3>::from_bytes(&wrong_version).unwrap_err(),
+            Errors::InvalidEncoding
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_to_bytes_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let cascade = Cascade::new([DirectForm1::<f32>::new(coeffs); 2]);
+
+        let bytes = cascade.to_bytes();
+        assert_eq!(bytes.len(), Cascade::<DirectForm1<f32>, 2>::ENCODED_LEN);
+
+        let decoded = Cascade::<DirectForm1<f32>, 2>::from_bytes(&bytes).unwrap();
+        for stage in decoded.stages() {
+            assert_eq!(stage.coefficients().canonical_key(), coeffs.canonical_key());
+        }
+    }
+
+    #[test]
+    fn test_cascade_bytes_round_trip_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let stage = |filter, f0: f64| {
+            DirectForm2Transposed::<f64>::new(
+                Coefficients::<f64>::from_params(filter, fs, f0.hz(), 0.8).unwrap(),
+            )
+        };
+        let cascade = Cascade::new([
+            stage(Type::HighPass, 30.0),
+            stage(Type::PeakingEQ(-3.0), 800.0),
+            stage(Type::HighShelf(2.0), 8_000.0),
+        ]);
+
+        let mut bytes = [0u8; Cascade::<DirectForm2Transposed<f64>, 3>::ENCODED_LEN];
+        assert_eq!(cascade.write_bytes(&mut bytes), 4 + 3 * 5 * 8);
+        assert_eq!(&bytes[..4], &[1, 8, 3, 0]);
+
+        let decoded = Cascade::<DirectForm2Transposed<f64>, 3>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.stages().len(), 3);
+        for (a, b) in cascade.stages().iter().zip(decoded.stages()) {
+            assert_eq!(
+                a.coefficients().canonical_key(),
+                b.coefficients().canonical_key()
+            );
+        }
+
+        // Mismatching stage counts, float sizes and truncated data are rejected
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f64>, 2>::from_bytes(&bytes).unwrap_err(),
+            Errors::InvalidEncoding
+        );
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f64>, 3>::from_bytes(&bytes[..bytes.len() - 1])
+                .unwrap_err(),
+            Errors::InvalidEncoding
+        );
+        let mut wrong_version = bytes;
+        wrong_version[0] = 2;
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f64>, 3>::from_bytes(&wrong_version).unwrap_err(),
+            Errors::InvalidEncoding
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_to_bytes_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let cascade = Cascade::new([DirectForm1::<f64>::new(coeffs); 2]);
+
+        let bytes = cascade.to_bytes();
+        assert_eq!(bytes.len(), Cascade::<DirectForm1<f64>, 2>::ENCODED_LEN);
+
+        let decoded = Cascade::<DirectForm1<f64>, 2>::from_bytes(&bytes).unwrap();
+        for stage in decoded.stages() {
+            assert_eq!(stage.coefficients().canonical_key(), coeffs.canonical_key());
+        }
+    }
+
+    #[test]
+    fn test_max_internal_gain_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let gentle =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 10.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let resonator =
+            Coefficients::<f32>::from_params(Type::BandPass, fs, 1.khz(), 50.0).unwrap();
+
+        assert!(gentle.max_internal_gain() >= libm::sqrtf(gentle.impulse_energy(1000)) * 0.999);
+        assert!(resonator.max_internal_gain() > 2.0 * gentle.max_internal_gain());
+
+        let unstable = Coefficients { a2: 1.5, ..gentle };
+        assert!(unstable.max_internal_gain().is_infinite());
+    }
+
+    #[test]
+    fn test_max_internal_gain_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let gentle =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 10.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let resonator =
+            Coefficients::<f64>::from_params(Type::BandPass, fs, 1.khz(), 50.0).unwrap();
+
+        assert!(gentle.max_internal_gain() >= libm::sqrt(gentle.impulse_energy(1000)) * 0.999);
+        assert!(resonator.max_internal_gain() > 2.0 * gentle.max_internal_gain());
+
+        let unstable = Coefficients { a2: 1.5, ..gentle };
+        assert!(unstable.max_internal_gain().is_infinite());
+    }
+
+    #[test]
+    fn test_from_ba_f32() {
+        // scipy.signal.butter(2, 0.25)
+        let b = [0.09763107, 0.19526215, 0.09763107];
+        let a = [1.0, -0.94280904, 0.33333333];
+        let expected = Coefficients::<f32>::from_ba(&b, &a).unwrap();
+        assert_eq!(expected.b1, 0.19526215);
+        assert_eq!(expected.a1, -0.94280904);
+
+        let scaled =
+            Coefficients::<f32>::from_ba(&b.map(|x| 2.0 * x), &a.map(|x| 2.0 * x)).unwrap();
+        assert!(scaled.approx_eq(&expected, 1e-7));
+
+        // Shorter inputs are zero padded and zero high order terms are ignored
+        let gain = Coefficients::<f32>::from_ba(&[0.5], &[2.0, 0.0, 0.0, 0.0]).unwrap();
+        assert!(gain.approx_eq(
+            &Coefficients {
+                a1: 0.0,
+                a2: 0.0,
+                b0: 0.25,
+                b1: 0.0,
+                b2: 0.0
+            },
+            0.0
+        ));
+
+        assert_eq!(
+            Coefficients::<f32>::from_ba(&b, &[1.0, -0.5, 0.25, 0.1]).unwrap_err(),
+            Errors::OrderTooHigh
+        );
+        assert_eq!(
+            Coefficients::<f32>::from_ba(&[1.0, 0.0, 0.0, 0.5], &a).unwrap_err(),
+            Errors::OrderTooHigh
+        );
+        assert_eq!(
+            Coefficients::<f32>::from_ba(&b, &[0.0, 1.0]).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+        assert_eq!(
+            Coefficients::<f32>::from_ba(&b, &[]).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+    }
+
+    #[test]
+    fn test_from_ba_f64() {
+        // scipy.signal.butter(2, 0.25)
+        let b = [0.09763107, 0.19526215, 0.09763107];
+        let a = [1.0, -0.94280904, 0.33333333];
+        let expected = Coefficients::<f64>::from_ba(&b, &a).unwrap();
+        assert_eq!(expected.b1, 0.19526215);
+        assert_eq!(expected.a1, -0.94280904);
+
+        let scaled =
+            Coefficients::<f64>::from_ba(&b.map(|x| 2.0 * x), &a.map(|x| 2.0 * x)).unwrap();
+        assert!(scaled.approx_eq(&expected, 1e-7));
+
+        // Shorter inputs are zero padded and zero high order terms are ignored
+        let gain = Coefficients::<f64>::from_ba(&[0.5], &[2.0, 0.0, 0.0, 0.0]).unwrap();
+        assert!(gain.approx_eq(
+            &Coefficients {
+                a1: 0.0,
+                a2: 0.0,
+                b0: 0.25,
+                b1: 0.0,
+                b2: 0.0
+            },
+            0.0
+        ));
+
+        assert_eq!(
+            Coefficients::<f64>::from_ba(&b, &[1.0, -0.5, 0.25, 0.1]).unwrap_err(),
+            Errors::OrderTooHigh
+        );
+        assert_eq!(
+            Coefficients::<f64>::from_ba(&[1.0, 0.0, 0.0, 0.5], &a).unwrap_err(),
+            Errors::OrderTooHigh
+        );
+        assert_eq!(
+            Coefficients::<f64>::from_ba(&b, &[0.0, 1.0]).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+        assert_eq!(
+            Coefficients::<f64>::from_ba(&b, &[]).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+    }
+
+    #[test]
+    fn test_df2t_run_block_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(9.0), 48.khz(), 500.hz(), 4.0)
+                .unwrap();
+
+        // An odd length exercises the scalar tail
+        let input: [f32; 1023] = core::array::from_fn(|i| {
+            libm::sinf(i as f32 * 0.37) + if i % 50 == 0 { 1.0 } else { 0.0 }
+        });
+        let mut block = input;
+        let mut blocked = DirectForm2Transposed::<f32>::new(coeffs);
+        blocked.run_block(&mut block);
+
+        let mut scalar = DirectForm2Transposed::<f32>::new(coeffs);
+        let expected = input.map(|x| scalar.run(x));
+
+        // Not bit exact, the rounding differences stay within a small multiple of the peak
+        let peak = expected.iter().fold(0.0f32, |peak, y| peak.max(y.abs()));
+        let bound = 128.0 * f32::EPSILON * peak;
+        for (&y, &expected) in block.iter().zip(&expected) {
+            assert!((y - expected).abs() <= bound, "{} vs {}", y, expected);
+        }
+
+        // The states continue after the block within the same bound
+        assert!((blocked.s1 - scalar.s1).abs() <= bound);
+        assert!((blocked.s2 - scalar.s2).abs() <= bound);
+    }
+
+    #[test]
+    fn test_phase_at_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let phase = |f: f32| coeffs.phase_at(fs, f.hz());
+
+        assert!(phase(1.0).abs() < 1e-2);
+        // A second order low pass is at -90 degrees at its cutoff
+        assert!((phase(1_000.0) + core::f32::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!((phase(23_900.0) + core::f32::consts::PI).abs() < 0.05);
+
+        // The phase of a low pass falls monotonically towards Nyquist
+        let freqs = [50.0, 700.0, 3_000.0, 15_000.0, 23_000.0];
+        assert!(freqs.windows(2).all(|pair| phase(pair[1]) < phase(pair[0])));
+    }
+
+    #[test]
+    fn test_phase_at_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let phase = |f: f64| coeffs.phase_at(fs, f.hz());
+
+        assert!(phase(1.0).abs() < 1e-2);
+        // A second order low pass is at -90 degrees at its cutoff
+        assert!((phase(1_000.0) + core::f64::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!((phase(23_900.0) + core::f64::consts::PI).abs() < 0.05);
+
+        // The phase of a low pass falls monotonically towards Nyquist
+        let freqs = [50.0, 700.0, 3_000.0, 15_000.0, 23_000.0];
+        assert!(freqs.windows(2).all(|pair| phase(pair[1]) < phase(pair[0])));
+    }
+
+    #[test]
+    fn test_with_sample_rate_ratio_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let half_power_hz = |coeffs: &Coefficients<f32>| {
+            (1..24_000)
+                .map(|f| f as f32)
+                .find(|&f| {
+                    let (re, im) = coeffs.response_at(2.0 * core::f32::consts::PI * f / fs.hz());
+                    re * re + im * im < 0.5
+                })
+                .unwrap()
+        };
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 2.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let retuned = coeffs.with_sample_rate_ratio(2.0);
+
+        // Relative to the unchanged fs the cutoff halves, i.e. it stays at 2 kHz for 96 kHz
+        let cutoff = half_power_hz(&retuned);
+        assert!((cutoff - 1_000.0).abs() < 50.0);
+
+        let (re, im) = retuned.response_at(0.0);
+        assert!((libm::hypotf(re, im) - 1.0).abs() < 1e-3);
+
+        // A ratio of one leaves the filter unchanged
+        assert!(coeffs.with_sample_rate_ratio(1.0).approx_eq(&coeffs, 1e-5));
+    }
+
+    #[test]
+    fn test_with_sample_rate_ratio_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let half_power_hz = |coeffs: &Coefficients<f64>| {
+            (1..24_000)
+                .map(|f| f as f64)
+                .find(|&f| {
+                    let (re, im) = coeffs.response_at(2.0 * core::f64::consts::PI * f / fs.hz());
+                    re * re + im * im < 0.5
+                })
+                .unwrap()
+        };
+
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 2.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let retuned = coeffs.with_sample_rate_ratio(2.0);
+
+        // Relative to the unchanged fs the cutoff halves, i.e. it stays at 2 kHz for 96 kHz
+        let cutoff = half_power_hz(&retuned);
+        assert!((cutoff - 1_000.0).abs() < 50.0);
+
+        let (re, im) = retuned.response_at(0.0);
+        assert!((libm::hypot(re, im) - 1.0).abs() < 1e-3);
+
+        // A ratio of one leaves the filter unchanged
+        assert!(coeffs.with_sample_rate_ratio(1.0).approx_eq(&coeffs, 1e-5));
+    }
+
+    #[test]
+    fn test_tilt_eq_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        // The magnitude in dB at f, from a DFT of the impulse response
+        let gain_db = |tilt: &mut TiltEq<f32>, f: f32| {
+            tilt.reset_state();
+            let omega = 2.0 * core::f32::consts::PI * f / fs.hz();
+            let (mut re, mut im) = (0.0, 0.0);
+            for n in 0..4_800 {
+                let h = tilt.run(if n == 0 { 1.0 } else { 0.0 });
+                re += h * libm::cosf(omega * n as f32);
+                im -= h * libm::sinf(omega * n as f32);
+            }
+            10.0 * libm::log10f(re * re + im * im)
+        };
+
+        let mut tilt = TiltEq::<f32>::new(fs, 1.khz(), 6.0).unwrap();
+        assert_eq!(tilt.tilt_db(), 6.0);
+
+        let (low, pivot, high) = (
+            gain_db(&mut tilt, 50.0),
+            gain_db(&mut tilt, 1_000.0),
+            gain_db(&mut tilt, 20_000.0),
+        );
+        assert!((low + 3.0).abs() < 0.1);
+        assert!(pivot.abs() < 0.05);
+        assert!((high - 3.0).abs() < 0.1);
+
+        // Symmetric about the pivot, in octaves
+        let below = gain_db(&mut tilt, 250.0);
+        let above = gain_db(&mut tilt, 4_000.0);
+        assert!(below < 0.0 && above > 0.0);
+        assert!((below + above).abs() < 0.2);
+
+        tilt.set_tilt_db(0.0).unwrap();
+        for &f in &[50.0, 1_000.0, 20_000.0] {
+            assert!(gain_db(&mut tilt, f).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_tilt_eq_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        // The magnitude in dB at f, from a DFT of the impulse response
+        let gain_db = |tilt: &mut TiltEq<f64>, f: f64| {
+            tilt.reset_state();
+            let omega = 2.0 * core::f64::consts::PI * f / fs.hz();
+            let (mut re, mut im) = (0.0, 0.0);
+            for n in 0..4_800 {
+                let h = tilt.run(if n == 0 { 1.0 } else { 0.0 });
+                re += h * libm::cos(omega * n as f64);
+                im -= h * libm::sin(omega * n as f64);
+            }
+            10.0 * libm::log10(re * re + im * im)
+        };
+
+        let mut tilt = TiltEq::<f64>::new(fs, 1.khz(), 6.0).unwrap();
+        assert_eq!(tilt.tilt_db(), 6.0);
+
+        let (low, pivot, high) = (
+            gain_db(&mut tilt, 50.0),
+            gain_db(&mut tilt, 1_000.0),
+            gain_db(&mut tilt, 20_000.0),
+        );
+        assert!((low + 3.0).abs() < 0.1);
+        assert!(pivot.abs() < 0.05);
+        assert!((high - 3.0).abs() < 0.1);
+
+        // Symmetric about the pivot, in octaves
+        let below = gain_db(&mut tilt, 250.0);
+        let above = gain_db(&mut tilt, 4_000.0);
+        assert!(below < 0.0 && above > 0.0);
+        assert!((below + above).abs() < 0.2);
+
+        tilt.set_tilt_db(0.0).unwrap();
+        for &f in &[50.0, 1_000.0, 20_000.0] {
+            assert!(gain_db(&mut tilt, f).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_frequency_response_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 2.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+
+        let (magnitude, phase) = coeffs.frequency_response(f0, fs);
+        assert!((20.0 * libm::log10f(magnitude) + 3.0103).abs() < 1e-3);
+        assert!((phase + core::f32::consts::FRAC_PI_2).abs() < 1e-4);
+
+        // DC passes unchanged and Nyquist is fully rejected, everything stays finite
+        let (magnitude, phase) = coeffs.frequency_response(1.0.hz(), fs);
+        assert!((magnitude - 1.0).abs() < 1e-4 && phase.abs() < 1e-3);
+        let (magnitude, phase) = coeffs.frequency_response(24.khz(), fs);
+        assert!(magnitude < 1e-4 && phase.is_finite());
+    }
+
+    #[test]
+    fn test_frequency_response_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 2.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+
+        let (magnitude, phase) = coeffs.frequency_response(f0, fs);
+        assert!((20.0 * libm::log10(magnitude) + 3.0103).abs() < 1e-3);
+        assert!((phase + core::f64::consts::FRAC_PI_2).abs() < 1e-4);
+
+        // DC passes unchanged and Nyquist is fully rejected, everything stays finite
+        let (magnitude, phase) = coeffs.frequency_response(1.0.hz(), fs);
+        assert!((magnitude - 1.0).abs() < 1e-4 && phase.abs() < 1e-3);
+        let (magnitude, phase) = coeffs.frequency_response(24.khz(), fs);
+        assert!(magnitude < 1e-4 && phase.is_finite());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_warp_table_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let freqs: [Hertz<f32>; 3] = [100.hz(), 1.khz(), 20.khz()];
+
+        let table = Hertz::<f32>::warp_table(fs, &freqs);
+        assert_eq!(table.len(), 3);
+        for (&(requested, _), &f) in table.iter().zip(&freqs) {
+            assert_eq!(requested, f);
+        }
+
+        // Low frequencies barely move, high frequencies land noticeably lower
+        assert!((table[0].1.hz() - 100.0).abs() < 0.01);
+        assert!((table[1].1.hz() - 1_000.0).abs() < 5.0);
+        assert!(table[2].1.hz() < 15_000.0);
+        assert!(table[2].1.hz() < fs.hz() / 2.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_warp_table_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let freqs: [Hertz<f64>; 3] = [100.hz(), 1.khz(), 20.khz()];
+
+        let table = Hertz::<f64>::warp_table(fs, &freqs);
+        assert_eq!(table.len(), 3);
+        for (&(requested, _), &f) in table.iter().zip(&freqs) {
+            assert_eq!(requested, f);
+        }
+
+        // Low frequencies barely move, high frequencies land noticeably lower
+        assert!((table[0].1.hz() - 100.0).abs() < 0.01);
+        assert!((table[1].1.hz() - 1_000.0).abs() < 5.0);
+        assert!(table[2].1.hz() < 15_000.0);
+        assert!(table[2].1.hz() < fs.hz() / 2.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_run_automated_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        // A low pass sweeping down from 20 kHz to 200 Hz
+        let coeffs: Vec<Coefficients<f32>> = (0..256)
+            .map(|i| {
+                let f0 = 20_000.0 * libm::powf(0.01, i as f32 / 255.0);
+                Coefficients::<f32>::from_params(Type::LowPass, fs, f0.hz(), Q_BUTTERWORTH_F32)
+                    .unwrap()
+            })
+            .collect();
+        // Alternating samples, i.e. a tone at Nyquist
+        let input: Vec<f32> = (0..256)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+
+        let mut biquad = DirectForm1::<f32>::new(coeffs[0]);
+        let output = biquad.run_automated(&input, &coeffs);
+
+        let mut reference = DirectForm1::<f32>::new(coeffs[0]);
+        for ((&x, &c), &y) in input.iter().zip(&coeffs).zip(&output) {
+            reference.update_coefficients(c);
+            assert_eq!(reference.run(x), y);
+        }
+
+        // The high frequency content is removed as the cutoff sweeps down
+        let level = |range: core::ops::Range<usize>| {
+            output[range].iter().map(|y| y.abs()).fold(0.0f32, f32::max)
+        };
+        assert!(level(192..256) < 0.01 * level(0..64));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic]
+    fn test_run_automated_length_mismatch_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), 1.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+
+        biquad.run_automated(&[0.0; 3], &[coeffs; 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_run_automated_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        // A low pass sweeping down from 20 kHz to 200 Hz
+        let coeffs: Vec<Coefficients<f64>> = (0..256)
+            .map(|i| {
+                let f0 = 20_000.0 * libm::pow(0.01, i as f64 / 255.0);
+                Coefficients::<f64>::from_params(Type::LowPass, fs, f0.hz(), Q_BUTTERWORTH_F64)
+                    .unwrap()
+            })
+            .collect();
+        // Alternating samples, i.e. a tone at Nyquist
+        let input: Vec<f64> = (0..256)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+
+        let mut biquad = DirectForm1::<f64>::new(coeffs[0]);
+        let output = biquad.run_automated(&input, &coeffs);
+
+        let mut reference = DirectForm1::<f64>::new(coeffs[0]);
+        for ((&x, &c), &y) in input.iter().zip(&coeffs).zip(&output) {
+            reference.update_coefficients(c);
+            assert_eq!(reference.run(x), y);
+        }
+
+        // The high frequency content is removed as the cutoff sweeps down
+        let level = |range: core::ops::Range<usize>| {
+            output[range].iter().map(|y| y.abs()).fold(0.0f64, f64::max)
+        };
+        assert!(level(192..256) < 0.01 * level(0..64));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic]
+    fn test_run_automated_length_mismatch_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 1.khz(), 1.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f64>::new(coeffs);
+
+        biquad.run_automated(&[0.0; 3], &[coeffs; 2]);
+    }
+
+    #[test]
+    fn test_magnitude_db_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let notch = Coefficients::<f32>::from_params(Type::Notch, fs, f0, 2.0).unwrap();
+        let depth = notch.magnitude_db(f0, fs);
+        assert!((-300.0..-60.0).contains(&depth));
+
+        let allpass = Coefficients::<f32>::from_params(Type::AllPass, fs, f0, 2.0).unwrap();
+        for i in 1..100 {
+            let f = 20.0 * libm::powf(1_000.0, i as f32 / 100.0);
+            assert!(allpass.magnitude_db(f.hz(), fs).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_magnitude_db_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let notch = Coefficients::<f64>::from_params(Type::Notch, fs, f0, 2.0).unwrap();
+        let depth = notch.magnitude_db(f0, fs);
+        assert!((-300.0..-60.0).contains(&depth));
+
+        let allpass = Coefficients::<f64>::from_params(Type::AllPass, fs, f0, 2.0).unwrap();
+        for i in 1..100 {
+            let f = 20.0 * libm::pow(1_000.0, i as f64 / 100.0);
+            assert!(allpass.magnitude_db(f.hz(), fs).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_errors_is_recoverable() {
+        assert!(Errors::OutsideNyquist.is_recoverable());
+        assert!(Errors::NegativeQ.is_recoverable());
+        assert!(Errors::QTooSmall.is_recoverable());
+        assert!(Errors::InvalidDrive.is_recoverable());
+
+        assert!(!Errors::NegativeFrequency.is_recoverable());
+        assert!(!Errors::InvalidEncoding.is_recoverable());
+        assert!(!Errors::OrderTooHigh.is_recoverable());
+        assert!(!Errors::InvalidCoefficients.is_recoverable());
+        assert!(!Errors::Unstable.is_recoverable());
+        assert!(!Errors::MissingParameter.is_recoverable());
+        assert!(!Errors::NonFiniteOutput.is_recoverable());
+        assert!(!Errors::GainUnreachable.is_recoverable());
+    }
+
+    #[test]
+    fn test_linkwitz_riley_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 2.khz();
+        let low = Coefficients::<f32>::linkwitz_riley_lp(fs, f0).unwrap();
+        let high = Coefficients::<f32>::linkwitz_riley_hp(fs, f0).unwrap();
+
+        // The complex response of two sections in series
+        let response = |sections: &[Coefficients<f32>; 2], omega: f32| {
+            let (r1, i1) = sections[0].response_at(omega);
+            let (r2, i2) = sections[1].response_at(omega);
+            (r1 * r2 - i1 * i2, r1 * i2 + i1 * r2)
+        };
+
+        for i in 1..200 {
+            let omega = core::f32::consts::PI * i as f32 / 200.0;
+            let (lr, li) = response(&low, omega);
+            let (hr, hi) = response(&high, omega);
+
+            assert!((libm::hypotf(lr + hr, li + hi) - 1.0).abs() < 1e-4);
+        }
+
+        // Both are -6 dB and in phase at the crossover
+        let omega = 2.0 * core::f32::consts::PI * f0.hz() / fs.hz();
+        let (lr, li) = response(&low, omega);
+        let (hr, hi) = response(&high, omega);
+        assert!((libm::hypotf(lr, li) - 0.5).abs() < 1e-4);
+        assert!((libm::hypotf(hr, hi) - 0.5).abs() < 1e-4);
+        assert!((libm::atan2f(li, lr) - libm::atan2f(hi, hr)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_linkwitz_riley_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 2.khz();
+        let low = Coefficients::<f64>::linkwitz_riley_lp(fs, f0).unwrap();
+        let high = Coefficients::<f64>::linkwitz_riley_hp(fs, f0).unwrap();
+
+        // The complex response of two sections in series
+        let response = |sections: &[Coefficients<f64>; 2], omega: f64| {
+            let (r1, i1) = sections[0].response_at(omega);
+            let (r2, i2) = sections[1].response_at(omega);
+            (r1 * r2 - i1 * i2, r1 * i2 + i1 * r2)
+        };
+
+        for i in 1..200 {
+            let omega = core::f64::consts::PI * i as f64 / 200.0;
+            let (lr, li) = response(&low, omega);
+            let (hr, hi) = response(&high, omega);
+
+            assert!((libm::hypot(lr + hr, li + hi) - 1.0).abs() < 1e-4);
+        }
+
+        // Both are -6 dB and in phase at the crossover
+        let omega = 2.0 * core::f64::consts::PI * f0.hz() / fs.hz();
+        let (lr, li) = response(&low, omega);
+        let (hr, hi) = response(&high, omega);
+        assert!((libm::hypot(lr, li) - 0.5).abs() < 1e-4);
+        assert!((libm::hypot(hr, hi) - 0.5).abs() < 1e-4);
+        assert!((libm::atan2(li, lr) - libm::atan2(hi, hr)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_from_params_with_bandwidth_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let peaking = Type::PeakingEQ(6.0);
+
+        // One octave corresponds to Q = sqrt(2) when the warping is negligible
+        let f0: Hertz<f32> = 100.hz();
+        let octave = Coefficients::<f32>::from_params_with_bandwidth(
+            peaking,
+            fs,
+            f0,
+            BandwidthParam::BandwidthOctaves(1.0),
+        )
+        .unwrap();
+        let q =
+            Coefficients::<f32>::from_params(peaking, fs, f0, core::f32::consts::SQRT_2).unwrap();
+        assert!(octave.approx_eq(&q, 1e-6));
+
+        // Towards Nyquist the warping correction lowers Q, countering the bilinear compression
+        let high = Coefficients::<f32>::from_params_with_bandwidth(
+            Type::BandPass,
+            fs,
+            10.khz(),
+            BandwidthParam::BandwidthOctaves(1.0),
+        )
+        .unwrap();
+        assert!(high.estimate_q().unwrap() < core::f32::consts::SQRT_2);
+
+        // A shelf slope of 1 is the maximally flat shelf
+        let shelf = Coefficients::<f32>::from_params_with_bandwidth(
+            Type::HighShelf(-4.0),
+            fs,
+            2.khz(),
+            BandwidthParam::ShelfSlope(1.0),
+        )
+        .unwrap();
+        let maxflat = Coefficients::<f32>::high_shelf_maxflat(fs, 2.khz(), -4.0).unwrap();
+        assert!(shelf.approx_eq(&maxflat, 1e-6));
+
+        let plain = Coefficients::<f32>::from_params_with_bandwidth(
+            peaking,
+            fs,
+            f0,
+            BandwidthParam::Q(0.7),
+        )
+        .unwrap();
+        assert!(plain.approx_eq(
+            &Coefficients::<f32>::from_params(peaking, fs, f0, 0.7).unwrap(),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn test_from_params_with_bandwidth_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let peaking = Type::PeakingEQ(6.0);
+
+        // One octave corresponds to Q = sqrt(2) when the warping is negligible
+        let f0: Hertz<f64> = 100.hz();
+        let octave = Coefficients::<f64>::from_params_with_bandwidth(
+            peaking,
+            fs,
+            f0,
+            BandwidthParam::BandwidthOctaves(1.0),
+        )
+        .unwrap();
+        let q =
+            Coefficients::<f64>::from_params(peaking, fs, f0, core::f64::consts::SQRT_2).unwrap();
+        assert!(octave.approx_eq(&q, 1e-6));
+
+        // Towards Nyquist the warping correction lowers Q, countering the bilinear compression
+        let high = Coefficients::<f64>::from_params_with_bandwidth(
+            Type::BandPass,
+            fs,
+            10.khz(),
+            BandwidthParam::BandwidthOctaves(1.0),
+        )
+        .unwrap();
+        assert!(high.estimate_q().unwrap() < core::f64::consts::SQRT_2);
+
+        // A shelf slope of 1 is the maximally flat shelf
+        let shelf = Coefficients::<f64>::from_params_with_bandwidth(
+            Type::HighShelf(-4.0),
+            fs,
+            2.khz(),
+            BandwidthParam::ShelfSlope(1.0),
+        )
+        .unwrap();
+        let maxflat = Coefficients::<f64>::high_shelf_maxflat(fs, 2.khz(), -4.0).unwrap();
+        assert!(shelf.approx_eq(&maxflat, 1e-6));
+
+        let plain = Coefficients::<f64>::from_params_with_bandwidth(
+            peaking,
+            fs,
+            f0,
+            BandwidthParam::Q(0.7),
+        )
+        .unwrap();
+        assert!(plain.approx_eq(
+            &Coefficients::<f64>::from_params(peaking, fs, f0, 0.7).unwrap(),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn test_bessel_lowpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let bessel = Coefficients::<f32>::bessel_lowpass(fs, f0).unwrap();
+        let butterworth =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+
+        // Relative variation of the group delay in the passband, up to half the cutoff
+        let spread = |coeffs: &Coefficients<f32>| {
+            let delays = (0..=50).map(|i| {
+                coeffs.group_delay_at(core::f32::consts::PI * f0.hz() * i as f32 / 50.0 / fs.hz())
+            });
+            let (min, max) = delays.fold((f32::MAX, f32::MIN), |(min, max), d| {
+                (min.min(d), max.max(d))
+            });
+            (max - min) / max
+        };
+
+        assert!(spread(&bessel) < 0.5 * spread(&butterworth));
+        assert!(bessel.step_overshoot() < butterworth.step_overshoot());
+    }
+
+    #[test]
+    fn test_bessel_lowpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let bessel = Coefficients::<f64>::bessel_lowpass(fs, f0).unwrap();
+        let butterworth =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+
+        // Relative variation of the group delay in the passband, up to half the cutoff
+        let spread = |coeffs: &Coefficients<f64>| {
+            let delays = (0..=50).map(|i| {
+                coeffs.group_delay_at(core::f64::consts::PI * f0.hz() * i as f64 / 50.0 / fs.hz())
+            });
+            let (min, max) = delays.fold((f64::MAX, f64::MIN), |(min, max), d| {
+                (min.min(d), max.max(d))
+            });
+            (max - min) / max
+        };
+
+        assert!(spread(&bessel) < 0.5 * spread(&butterworth));
+        assert!(bessel.step_overshoot() < butterworth.step_overshoot());
+    }
+
+    #[test]
+    fn test_min_sample_rate_f32() {
+        let f0: Hertz<f32> = 20.khz();
+
+        assert!((f0.min_sample_rate(1.1).hz() - 44_000.0).abs() < 0.01);
+        assert_eq!(f0.min_sample_rate(1.0).hz(), 40_000.0);
+
+        // A filter at f0 can be built at the minimum sample rate
+        let fs = f0.min_sample_rate(1.1);
+        assert!(Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_min_sample_rate_negative_margin_f32() {
+        let f0: Hertz<f32> = 20.khz();
+        f0.min_sample_rate(-1.0);
+    }
+
+    #[test]
+    fn test_min_sample_rate_f64() {
+        let f0: Hertz<f64> = 20.khz();
+
+        assert!((f0.min_sample_rate(1.1).hz() - 44_000.0).abs() < 0.01);
+        assert_eq!(f0.min_sample_rate(1.0).hz(), 40_000.0);
+
+        // A filter at f0 can be built at the minimum sample rate
+        let fs = f0.min_sample_rate(1.1);
+        assert!(Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_min_sample_rate_negative_margin_f64() {
+        let f0: Hertz<f64> = 20.khz();
+        f0.min_sample_rate(-1.0);
+    }
+
+    #[test]
+    fn test_generic_coefficients_f32() {
+        fn design<T: Float>(filter: Type<T>) -> [T; 5] {
+            let fs = Hertz::<T>::from_hz(T::from_f64(48_000.0)).unwrap();
+            let f0 = Hertz::<T>::from_hz(T::from_f64(1_000.0)).unwrap();
+            let c = Coefficients::<T>::from_params(filter, fs, f0, T::from_f64(0.9)).unwrap();
+            [c.b0, c.b1, c.b2, c.a1, c.a2]
+        }
+
+        // Values of the separate f32 implementation before it was made generic
+        assert_eq!(
+            design::<f32>(Type::LowPass),
+            [
+                0.0039883554,
+                0.007976711,
+                0.0039883554,
+                -1.8488232,
+                0.86477655
+            ]
+        );
+        assert_eq!(
+            design::<f32>(Type::PeakingEQ(6.0)),
+            [1.0485983, -1.886066, 0.8537424, -1.886066, 0.9023407]
+        );
+        assert_eq!(
+            design::<f32>(Type::HighShelf(4.5)),
+            [1.6461548, -3.074497, 1.4487975, -1.827226, 0.8476813]
+        );
+    }
+
+    #[test]
+    fn test_generic_coefficients_f64() {
+        fn design<T: Float>(filter: Type<T>) -> [T; 5] {
+            let fs = Hertz::<T>::from_hz(T::from_f64(48_000.0)).unwrap();
+            let f0 = Hertz::<T>::from_hz(T::from_f64(1_000.0)).unwrap();
+            let c = Coefficients::<T>::from_params(filter, fs, f0, T::from_f64(0.9)).unwrap();
+            [c.b0, c.b1, c.b2, c.a1, c.a2]
+        }
+
+        // Values of the separate f64 implementation before it was made generic
+        assert_eq!(
+            design::<f64>(Type::LowPass),
+            [
+                0.003988355503592677,
+                0.007976711007185354,
+                0.003988355503592677,
+                -1.848823142275648,
+                0.8647765642900187
+            ]
+        );
+        assert_eq!(
+            design::<f64>(Type::PeakingEQ(6.0)),
+            [
+                1.0485982951916846,
+                -1.8860659420927093,
+                0.8537424355669114,
+                -1.8860659420927093,
+                0.9023407307585959
+            ]
+        );
+        assert_eq!(
+            design::<f64>(Type::HighShelf(4.5)),
+            [
+                1.6461547698406105,
+                -3.074496940288569,
+                1.4487974374997223,
+                -1.827226018309047,
+                0.8476812853608109
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_stable_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let butterworth =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(butterworth.is_stable());
+
+        // Poles at radius 1.1
+        let unstable = Coefficients::<f32> {
+            a1: -1.0,
+            a2: 1.21,
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+        };
+        assert!(!unstable.is_stable());
+
+        // A real pole on the unit circle is not strictly inside
+        let marginal = Coefficients::<f32> {
+            a1: -1.0,
+            a2: 0.0,
+            ..butterworth
+        };
+        assert!(!marginal.is_stable());
+
+        let nan = Coefficients::<f32> {
+            b1: f32::NAN,
+            ..butterworth
+        };
+        assert!(!nan.is_stable());
+        let inf = Coefficients::<f32> {
+            a1: f32::INFINITY,
+            ..butterworth
+        };
+        assert!(!inf.is_stable());
+    }
+
+    #[test]
+    fn test_is_stable_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let butterworth =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(butterworth.is_stable());
+
+        // Poles at radius 1.1
+        let unstable = Coefficients::<f64> {
+            a1: -1.0,
+            a2: 1.21,
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+        };
+        assert!(!unstable.is_stable());
+
+        // A real pole on the unit circle is not strictly inside
+        let marginal = Coefficients::<f64> {
+            a1: -1.0,
+            a2: 0.0,
+            ..butterworth
+        };
+        assert!(!marginal.is_stable());
+
+        let nan = Coefficients::<f64> {
+            b1: f64::NAN,
+            ..butterworth
+        };
+        assert!(!nan.is_stable());
+        let inf = Coefficients::<f64> {
+            a1: f64::INFINITY,
+            ..butterworth
+        };
+        assert!(!inf.is_stable());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_morph_via_convolution_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let low =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        let high =
+            Coefficients::<f32>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+
+        let impulse_response = |coeffs: Coefficients<f32>| {
+            let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+            (0..64)
+                .map(|n| biquad.run(if n == 0 { 1.0 } else { 0.0 }))
+                .collect::<Vec<f32>>()
+        };
+
+        assert_eq!(
+            Coefficients::morph_via_convolution(&low, &high, 0.0, 64),
+            impulse_response(low)
+        );
+        assert_eq!(
+            Coefficients::morph_via_convolution(&low, &high, 1.0, 64),
+            impulse_response(high)
+        );
+
+        let half = Coefficients::morph_via_convolution(&low, &high, 0.5, 64);
+        assert_eq!(half.len(), 64);
+        assert!((half[0] - 0.5 * (low.b0 + high.b0)).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_morph_via_convolution_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let low =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        let high =
+            Coefficients::<f64>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+
+        let impulse_response = |coeffs: Coefficients<f64>| {
+            let mut biquad = DirectForm2Transposed::<f64>::new(coeffs);
+            (0..64)
+                .map(|n| biquad.run(if n == 0 { 1.0 } else { 0.0 }))
+                .collect::<Vec<f64>>()
+        };
+
+        assert_eq!(
+            Coefficients::morph_via_convolution(&low, &high, 0.0, 64),
+            impulse_response(low)
+        );
+        assert_eq!(
+            Coefficients::morph_via_convolution(&low, &high, 1.0, 64),
+            impulse_response(high)
+        );
+
+        let half = Coefficients::morph_via_convolution(&low, &high, 0.5, 64);
+        assert_eq!(half.len(), 64);
+        assert!((half[0] - 0.5 * (low.b0 + high.b0)).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_flush_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::BandPass, fs, f0, 20.0).unwrap();
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+        biquad.run(1.0);
+
+        let tail = biquad.flush(1 << 20);
+        assert!(tail.len() > 1000 && tail.len() < 1 << 20);
+        assert!(tail[tail.len() - 2..].iter().all(|y| y.abs() < 1e-6));
+
+        // The ring decays over the tail
+        let peak = |block: &[f32]| block.iter().fold(0.0f32, |max, y| max.max(y.abs()));
+        let quarter = tail.len() / 4;
+        assert!(peak(&tail[3 * quarter..]) < 0.01 * peak(&tail[..quarter]));
+
+        // The length limit is respected
+        biquad.run(1.0);
+        assert_eq!(biquad.flush(100).len(), 100);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_flush_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::BandPass, fs, f0, 20.0).unwrap();
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+        biquad.run(1.0);
+
+        let tail = biquad.flush(1 << 20);
+        assert!(tail.len() > 1000 && tail.len() < 1 << 20);
+        assert!(tail[tail.len() - 2..].iter().all(|y| y.abs() < 1e-6));
+
+        // The ring decays over the tail
+        let peak = |block: &[f64]| block.iter().fold(0.0f64, |max, y| max.max(y.abs()));
+        let quarter = tail.len() / 4;
+        assert!(peak(&tail[3 * quarter..]) < 0.01 * peak(&tail[..quarter]));
+
+        // The length limit is respected
+        biquad.run(1.0);
+        assert_eq!(biquad.flush(100).len(), 100);
+    }
+
+    #[test]
+    fn test_coefficients_new_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let designed =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+
+        let coeffs = Coefficients::<f32>::new(
+            designed.b0,
+            designed.b1,
+            designed.b2,
+            designed.a1,
+            designed.a2,
+        )
+        .unwrap();
+        assert!(coeffs.approx_eq(&designed, 0.0));
+
+        // Poles at radius 1.1
+        assert_eq!(
+            Coefficients::<f32>::new(1.0, 0.0, 0.0, -1.0, 1.21).unwrap_err(),
+            Errors::Unstable
+        );
+        assert_eq!(
+            Coefficients::<f32>::new(f32::NAN, 0.0, 0.0, 0.0, 0.0).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+    }
+
+    #[test]
+    fn test_coefficients_new_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let designed =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+
+        let coeffs = Coefficients::<f64>::new(
+            designed.b0,
+            designed.b1,
+            designed.b2,
+            designed.a1,
+            designed.a2,
+        )
+        .unwrap();
+        assert!(coeffs.approx_eq(&designed, 0.0));
+
+        // Poles at radius 1.1
+        assert_eq!(
+            Coefficients::<f64>::new(1.0, 0.0, 0.0, -1.0, 1.21).unwrap_err(),
+            Errors::Unstable
+        );
+        assert_eq!(
+            Coefficients::<f64>::new(f64::NAN, 0.0, 0.0, 0.0, 0.0).unwrap_err(),
+            Errors::InvalidCoefficients
+        );
+    }
+
+    #[test]
+    fn test_update_from_params_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let mut coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        for &(filter, f0) in &[
+            (Type::LowPass, 2.khz()),
+            (Type::SinglePoleLowPass, 500.hz()),
+            (Type::PeakingEQ(-4.0), 3.khz()),
+        ] {
+            let fresh = Coefficients::<f32>::from_params(filter, fs, f0, 0.8).unwrap();
+            coeffs.update_from_params(filter, fs, f0, 0.8).unwrap();
+            assert_eq!(coeffs.canonical_key(), fresh.canonical_key());
+        }
+
+        // Errors leave the coefficients untouched
+        let before = coeffs.canonical_key();
+        assert_eq!(
+            coeffs.update_from_params(Type::LowPass, fs, 30.khz(), 0.8),
+            Err(Errors::OutsideNyquist)
+        );
+        assert_eq!(coeffs.canonical_key(), before);
+    }
+
+    #[test]
+    fn test_update_from_params_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let mut coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        for &(filter, f0) in &[
+            (Type::LowPass, 2.khz()),
+            (Type::SinglePoleLowPass, 500.hz()),
+            (Type::PeakingEQ(-4.0), 3.khz()),
+        ] {
+            let fresh = Coefficients::<f64>::from_params(filter, fs, f0, 0.8).unwrap();
+            coeffs.update_from_params(filter, fs, f0, 0.8).unwrap();
+            assert_eq!(coeffs.canonical_key(), fresh.canonical_key());
+        }
+
+        // Errors leave the coefficients untouched
+        let before = coeffs.canonical_key();
+        assert_eq!(
+            coeffs.update_from_params(Type::LowPass, fs, 30.khz(), 0.8),
+            Err(Errors::OutsideNyquist)
+        );
+        assert_eq!(coeffs.canonical_key(), before);
+    }
+
+    #[test]
+    fn test_presence_boost_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs = Coefficients::<f32>::presence_boost(fs, 4.0).unwrap();
+
+        assert!((coeffs.magnitude_db(4.khz(), fs) - 4.0).abs() < 0.01);
+        assert!(coeffs.magnitude_db(3.khz(), fs) > 3.0);
+        assert!(coeffs.magnitude_db(5.khz(), fs) > 3.0);
+        assert!(coeffs.magnitude_db(100.hz(), fs).abs() < 0.1);
+        assert!(coeffs.magnitude_db(500.hz(), fs).abs() < 0.5);
+
+        let low_fs: Hertz<f32> = 6.khz();
+        assert!(Coefficients::<f32>::presence_boost(low_fs, 4.0).is_err());
+    }
+
+    #[test]
+    fn test_presence_boost_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs = Coefficients::<f64>::presence_boost(fs, 4.0).unwrap();
+
+        assert!((coeffs.magnitude_db(4.khz(), fs) - 4.0).abs() < 0.01);
+        assert!(coeffs.magnitude_db(3.khz(), fs) > 3.0);
+        assert!(coeffs.magnitude_db(5.khz(), fs) > 3.0);
+        assert!(coeffs.magnitude_db(100.hz(), fs).abs() < 0.1);
+        assert!(coeffs.magnitude_db(500.hz(), fs).abs() < 0.5);
+
+        let low_fs: Hertz<f64> = 6.khz();
+        assert!(Coefficients::<f64>::presence_boost(low_fs, 4.0).is_err());
+    }
+
+    #[test]
+    fn test_process_block_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(6.0), fs, 2.khz(), 2.0).unwrap();
+        let input: [f32; 257] = core::array::from_fn(|i| libm::sinf(i as f32 * 0.3) + 0.1);
+
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let expected: [f32; 257] = core::array::from_fn(|i| reference.run(input[i]));
+
+        // Split into two blocks to check that the state carries over
+        let mut output = [0.0f32; 257];
+        let mut df1 = DirectForm1::<f32>::new(coeffs);
+        df1.process_block(&input[..100], &mut output[..100]);
+        df1.process_block(&input[100..], &mut output[100..]);
+        assert_eq!(output, expected);
+
+        let mut reference = DirectForm2Transposed::<f32>::new(coeffs);
+        let expected: [f32; 257] = core::array::from_fn(|i| reference.run(input[i]));
+
+        let mut buf = input;
+        let mut df2t = DirectForm2Transposed::<f32>::new(coeffs);
+        df2t.process_block_in_place(&mut buf[..100]);
+        df2t.process_block_in_place(&mut buf[100..]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_process_block_length_mismatch_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, 2.khz(), 0.7).unwrap();
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+        biquad.process_block(&[0.0; 4], &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn test_process_block_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(6.0), fs, 2.khz(), 2.0).unwrap();
+        let input: [f64; 257] = core::array::from_fn(|i| libm::sin(i as f64 * 0.3) + 0.1);
+
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let expected: [f64; 257] = core::array::from_fn(|i| reference.run(input[i]));
+
+        // Split into two blocks to check that the state carries over
+        let mut output = [0.0f64; 257];
+        let mut df1 = DirectForm1::<f64>::new(coeffs);
+        df1.process_block(&input[..100], &mut output[..100]);
+        df1.process_block(&input[100..], &mut output[100..]);
+        assert_eq!(output, expected);
+
+        let mut reference = DirectForm2Transposed::<f64>::new(coeffs);
+        let expected: [f64; 257] = core::array::from_fn(|i| reference.run(input[i]));
+
+        let mut buf = input;
+        let mut df2t = DirectForm2Transposed::<f64>::new(coeffs);
+        df2t.process_block_in_place(&mut buf[..100]);
+        df2t.process_block_in_place(&mut buf[100..]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_process_block_length_mismatch_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, fs, 2.khz(), 0.7).unwrap();
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+        biquad.process_block(&[0.0; 4], &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn test_as_array_ref_f32() {
+        let coeffs = Coefficients::<f32> {
+            a1: 1.0,
+            a2: 2.0,
+            b0: 3.0,
+            b1: 4.0,
+            b2: 5.0,
+        };
+        assert_eq!(coeffs.as_array_ref(), &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            core::mem::size_of::<Coefficients<f32>>(),
+            core::mem::size_of::<[f32; 5]>()
+        );
+
+        // Consecutive coefficients are contiguous
+        let bank = [coeffs, Coefficients::<f32> { a1: 6.0, ..coeffs }];
+        assert_eq!(bank[1].as_array_ref()[0], 6.0);
+        assert_eq!(
+            bank[1].as_array_ref().as_ptr(),
+            bank[0].as_array_ref().as_ptr().wrapping_add(5)
+        );
+    }
+
+    #[test]
+    fn test_as_array_ref_f64() {
+        let coeffs = Coefficients::<f64> {
+            a1: 1.0,
+            a2: 2.0,
+            b0: 3.0,
+            b1: 4.0,
+            b2: 5.0,
+        };
+        assert_eq!(coeffs.as_array_ref(), &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            core::mem::size_of::<Coefficients<f64>>(),
+            core::mem::size_of::<[f64; 5]>()
+        );
+
+        // Consecutive coefficients are contiguous
+        let bank = [coeffs, Coefficients::<f64> { a1: 6.0, ..coeffs }];
+        assert_eq!(bank[1].as_array_ref()[0], 6.0);
+        assert_eq!(
+            bank[1].as_array_ref().as_ptr(),
+            bank[0].as_array_ref().as_ptr().wrapping_add(5)
+        );
+    }
+
+    #[test]
+    fn test_is_allpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 2.khz();
+        let allpass = Coefficients::<f32>::from_params(Type::AllPass, fs, f0, 0.7).unwrap();
+        assert!(allpass.is_allpass(1e-6));
+
+        // An inverted allpass is still flat
+        let inverted = Coefficients::<f32> {
+            b0: -allpass.b0,
+            b1: -allpass.b1,
+            b2: -allpass.b2,
+            ..allpass
+        };
+        assert!(inverted.is_allpass(1e-6));
+
+        let lowpass = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 0.7).unwrap();
+        assert!(!lowpass.is_allpass(1e-6));
+
+        let broken = Coefficients::<f32> {
+            b1: allpass.b1 + 1e-3,
+            ..allpass
+        };
+        assert!(!broken.is_allpass(1e-6));
+    }
+
+    #[test]
+    fn test_is_allpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 2.khz();
+        let allpass = Coefficients::<f64>::from_params(Type::AllPass, fs, f0, 0.7).unwrap();
+        assert!(allpass.is_allpass(1e-6));
+
+        // An inverted allpass is still flat
+        let inverted = Coefficients::<f64> {
+            b0: -allpass.b0,
+            b1: -allpass.b1,
+            b2: -allpass.b2,
+            ..allpass
+        };
+        assert!(inverted.is_allpass(1e-6));
+
+        let lowpass = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 0.7).unwrap();
+        assert!(!lowpass.is_allpass(1e-6));
+
+        let broken = Coefficients::<f64> {
+            b1: allpass.b1 + 1e-3,
+            ..allpass
+        };
+        assert!(!broken.is_allpass(1e-6));
+    }
+
+    #[test]
+    fn test_filter_iter_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, 2.khz(), 0.7).unwrap();
+        let input: [f32; 64] = core::array::from_fn(|i| libm::sinf(i as f32 * 0.7));
+
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let expected: [f32; 64] = core::array::from_fn(|i| reference.run(input[i]));
+
+        // The borrowed filter keeps its state between iterators
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+        let mut output = [0.0f32; 64];
+        for (out, y) in output
+            .iter_mut()
+            .zip(biquad.filter_iter(input[..32].iter().copied()))
+        {
+            *out = y;
+        }
+        for (out, y) in output[32..]
+            .iter_mut()
+            .zip(biquad.filter_iter(input[32..].iter().copied()))
+        {
+            *out = y;
+        }
+        assert_eq!(output, expected);
+
+        let mut iter = DirectForm1::<f32>::new(coeffs).into_filter_iter(input.iter().copied());
+        assert_eq!(iter.size_hint(), (64, Some(64)));
+        assert!(iter
+            .by_ref()
+            .take(64)
+            .zip(expected.iter())
+            .all(|(y, &e)| y == e));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_filter_iter_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, fs, 2.khz(), 0.7).unwrap();
+        let input: [f64; 64] = core::array::from_fn(|i| libm::sin(i as f64 * 0.7));
+
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let expected: [f64; 64] = core::array::from_fn(|i| reference.run(input[i]));
+
+        // The borrowed filter keeps its state between iterators
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+        let mut output = [0.0f64; 64];
+        for (out, y) in output
+            .iter_mut()
+            .zip(biquad.filter_iter(input[..32].iter().copied()))
+        {
+            *out = y;
+        }
+        for (out, y) in output[32..]
+            .iter_mut()
+            .zip(biquad.filter_iter(input[32..].iter().copied()))
+        {
+            *out = y;
+        }
+        assert_eq!(output, expected);
+
+        let mut iter = DirectForm1::<f64>::new(coeffs).into_filter_iter(input.iter().copied());
+        assert_eq!(iter.size_hint(), (64, Some(64)));
+        assert!(iter
+            .by_ref()
+            .take(64)
+            .zip(expected.iter())
+            .all(|(y, &e)| y == e));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_match_eq_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let bump = Coefficients::<f32>::from_params(Type::PeakingEQ(6.0), fs, f0, 1.4).unwrap();
+
+        // Flat source and bumped target on a log frequency grid from 20 Hz to 20 kHz
+        let grid: Vec<Hertz<f32>> = (0..200)
+            .map(|i| Hertz::<f32>::from_hz(20.0 * libm::powf(1000.0, i as f32 / 199.0)).unwrap())
+            .collect();
+        let source: Vec<(Hertz<f32>, f32)> = grid.iter().map(|&f| (f, 0.0)).collect();
+        let target: Vec<(Hertz<f32>, f32)> = grid
+            .iter()
+            .map(|&f| (f, bump.magnitude_db(f, fs)))
+            .collect();
+
+        let bands = Coefficients::<f32>::match_eq(fs, &source, &target, 4);
+        assert!(!bands.is_empty() && bands.len() <= 4);
+
+        // The first band recovers the bump
+        let first = bands[0];
+        assert!((first.magnitude_db(f0, fs) - 6.0).abs() < 0.5);
+        assert!(first.magnitude_db(100.hz(), fs).abs() < 1.0);
+
+        // All bands together match the target
+        for &(f, db) in &target {
+            let matched: f32 = bands.iter().map(|band| band.magnitude_db(f, fs)).sum();
+            assert!(
+                (matched - db).abs() < 0.5,
+                "{} dB instead of {} dB",
+                matched,
+                db
+            );
+        }
+
+        // Matching a curve to itself needs no bands
+        assert!(Coefficients::<f32>::match_eq(fs, &target, &target, 4).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_match_eq_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let bump = Coefficients::<f64>::from_params(Type::PeakingEQ(6.0), fs, f0, 1.4).unwrap();
+
+        // Flat source and bumped target on a log frequency grid from 20 Hz to 20 kHz
+        let grid: Vec<Hertz<f64>> = (0..200)
+            .map(|i| Hertz::<f64>::from_hz(20.0 * libm::pow(1000.0, i as f64 / 199.0)).unwrap())
+            .collect();
+        let source: Vec<(Hertz<f64>, f64)> = grid.iter().map(|&f| (f, 0.0)).collect();
+        let target: Vec<(Hertz<f64>, f64)> = grid
+            .iter()
+            .map(|&f| (f, bump.magnitude_db(f, fs)))
+            .collect();
+
+        let bands = Coefficients::<f64>::match_eq(fs, &source, &target, 4);
+        assert!(!bands.is_empty() && bands.len() <= 4);
+
+        // The first band recovers the bump
+        let first = bands[0];
+        assert!((first.magnitude_db(f0, fs) - 6.0).abs() < 0.5);
+        assert!(first.magnitude_db(100.hz(), fs).abs() < 1.0);
+
+        // All bands together match the target
+        for &(f, db) in &target {
+            let matched: f64 = bands.iter().map(|band| band.magnitude_db(f, fs)).sum();
+            assert!(
+                (matched - db).abs() < 0.5,
+                "{} dB instead of {} dB",
+                matched,
+                db
+            );
+        }
+
+        // Matching a curve to itself needs no bands
+        assert!(Coefficients::<f64>::match_eq(fs, &target, &target, 4).is_empty());
+    }
+
+    #[test]
+    fn test_magnitude_response_into_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let (f_min, f_max): (Hertz<f32>, Hertz<f32>) = (20.hz(), 20.khz());
+        let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), 0.7).unwrap();
+
+        let mut out = [0.0f32; 64];
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut out);
+
+        // Every point is at its logarithmically spaced frequency
+        for (i, &db) in out.iter().enumerate() {
+            let f = 20.0 * libm::pow(1_000.0, i as f64 / 63.0);
+            let expected = coeffs.magnitude_db(Hertz::<f32>::from_hz(f as f32).unwrap(), fs);
+            assert!((db - expected).abs() < 1e-3, "{} dB instead of {} dB", db, expected);
+        }
+
+        // An empty slice is left alone and a single point is at `f_min`
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut []);
+        let mut single = [0.0f32];
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut single);
+        assert_eq!(single[0], coeffs.magnitude_db(f_min, fs));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_magnitude_response_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let (f_min, f_max): (Hertz<f32>, Hertz<f32>) = (20.hz(), 20.khz());
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(3.0), fs, 1.khz(), 2.0).unwrap();
+
+        let response = coeffs.magnitude_response(fs, f_min, f_max, 100);
+        assert_eq!(response.len(), 100);
+        for (i, &db) in response.iter().enumerate() {
+            let f = 20.0 * libm::pow(1_000.0, i as f64 / 99.0);
+            let expected = coeffs.magnitude_db(Hertz::<f32>::from_hz(f as f32).unwrap(), fs);
+            assert!((db - expected).abs() < 1e-3, "{} dB instead of {} dB", db, expected);
+        }
+
+        assert!(coeffs.magnitude_response(fs, f_min, f_max, 0).is_empty());
+        assert_eq!(
+            coeffs.magnitude_response(fs, f_min, f_max, 1),
+            vec![coeffs.magnitude_db(f_min, fs)]
+        );
+    }
+
+    #[test]
+    fn test_magnitude_response_into_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let (f_min, f_max): (Hertz<f64>, Hertz<f64>) = (20.hz(), 20.khz());
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), 0.7).unwrap();
+
+        let mut out = [0.0f64; 64];
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut out);
+
+        // Every point is at its logarithmically spaced frequency
+        for (i, &db) in out.iter().enumerate() {
+            let f = 20.0 * libm::pow(1_000.0, i as f64 / 63.0);
+            let expected = coeffs.magnitude_db(Hertz::<f64>::from_hz(f as f64).unwrap(), fs);
+            assert!((db - expected).abs() < 1e-3, "{} dB instead of {} dB", db, expected);
+        }
+
+        // An empty slice is left alone and a single point is at `f_min`
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut []);
+        let mut single = [0.0f64];
+        coeffs.magnitude_response_into(fs, f_min, f_max, &mut single);
+        assert_eq!(single[0], coeffs.magnitude_db(f_min, fs));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_magnitude_response_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let (f_min, f_max): (Hertz<f64>, Hertz<f64>) = (20.hz(), 20.khz());
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(3.0), fs, 1.khz(), 2.0).unwrap();
+
+        let response = coeffs.magnitude_response(fs, f_min, f_max, 100);
+        assert_eq!(response.len(), 100);
+        for (i, &db) in response.iter().enumerate() {
+            let f = 20.0 * libm::pow(1_000.0, i as f64 / 99.0);
+            let expected = coeffs.magnitude_db(Hertz::<f64>::from_hz(f as f64).unwrap(), fs);
+            assert!((db - expected).abs() < 1e-3, "{} dB instead of {} dB", db, expected);
+        }
+
+        assert!(coeffs.magnitude_response(fs, f_min, f_max, 0).is_empty());
+        assert_eq!(
+            coeffs.magnitude_response(fs, f_min, f_max, 1),
+            vec![coeffs.magnitude_db(f_min, fs)]
+        );
+    }
+
+    #[test]
+    fn test_cascade_from_coefficients_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        // 4th order Butterworth from the Qs of its two second order sections
+        let sections = [0.541_196_1, 1.306_563]
+            .map(|q| Coefficients::<f32>::from_params(Type::LowPass, fs, f0, q).unwrap());
+        let mut cascade = Cascade::<DirectForm2Transposed<f32>, 2>::from_coefficients(sections);
+
+        let amplitude = |cascade: &mut Cascade<DirectForm2Transposed<f32>, 2>, f: f32| {
+            cascade.reset_state();
+            let omega = 2.0 * core::f32::consts::PI * f / fs.hz();
+            let mut buf: [f32; 9600] = core::array::from_fn(|n| libm::sinf(omega * n as f32));
+            cascade.process_block_in_place(&mut buf);
+            buf[4800..].iter().fold(0.0f32, |max, y| max.max(y.abs()))
+        };
+
+        let to_db = |gain: f32| 20.0 * libm::log10f(gain);
+        assert!((to_db(amplitude(&mut cascade, 1000.0)) + 3.01).abs() < 0.1);
+        assert!(to_db(amplitude(&mut cascade, 250.0)).abs() < 0.1);
+        // 24 dB per octave roll off
+        assert!((to_db(amplitude(&mut cascade, 4000.0)) + 48.0).abs() < 2.0);
+
+        // Block processing matches running sample by sample
+        let input: [f32; 16] = core::array::from_fn(|n| n as f32);
+        let mut reference = Cascade::<DirectForm1<f32>, 2>::from_coefficients(sections);
+        let expected: [f32; 16] = core::array::from_fn(|n| reference.run(input[n]));
+        let mut output = [0.0f32; 16];
+        Cascade::<DirectForm1<f32>, 2>::from_coefficients(sections)
+            .process_block(&input, &mut output);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_cascade_from_coefficients_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        // 4th order Butterworth from the Qs of its two second order sections
+        let sections = [0.541_196_100_146_197, 1.306_562_964_876_376_6]
+            .map(|q| Coefficients::<f64>::from_params(Type::LowPass, fs, f0, q).unwrap());
+        let mut cascade = Cascade::<DirectForm2Transposed<f64>, 2>::from_coefficients(sections);
+
+        let amplitude = |cascade: &mut Cascade<DirectForm2Transposed<f64>, 2>, f: f64| {
+            cascade.reset_state();
+            let omega = 2.0 * core::f64::consts::PI * f / fs.hz();
+            let mut buf: [f64; 9600] = core::array::from_fn(|n| libm::sin(omega * n as f64));
+            cascade.process_block_in_place(&mut buf);
+            buf[4800..].iter().fold(0.0f64, |max, y| max.max(y.abs()))
+        };
+
+        let to_db = |gain: f64| 20.0 * libm::log10(gain);
+        assert!((to_db(amplitude(&mut cascade, 1000.0)) + 3.01).abs() < 0.1);
+        assert!(to_db(amplitude(&mut cascade, 250.0)).abs() < 0.1);
+        // 24 dB per octave roll off
+        assert!((to_db(amplitude(&mut cascade, 4000.0)) + 48.0).abs() < 2.0);
+
+        // Block processing matches running sample by sample
+        let input: [f64; 16] = core::array::from_fn(|n| n as f64);
+        let mut reference = Cascade::<DirectForm1<f64>, 2>::from_coefficients(sections);
+        let expected: [f64; 16] = core::array::from_fn(|n| reference.run(input[n]));
+        let mut output = [0.0f64; 16];
+        Cascade::<DirectForm1<f64>, 2>::from_coefficients(sections)
+            .process_block(&input, &mut output);
+        assert_eq!(output, expected);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_butterworth_lowpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let second = Coefficients::<f32>::butterworth_lowpass(2, fs, f0).unwrap();
+        let reference =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert_eq!(second.len(), 1);
+        assert!(second[0].approx_eq(&reference, 1e-6));
+
+        // The bilinear transform of the analog Butterworth response, prewarped at f0
+        let expected_db = |order: i32, f: f32| {
+            let pi = core::f32::consts::PI;
+            let ratio = libm::tanf(pi * f / fs.hz()) / libm::tanf(pi * f0.hz() / fs.hz());
+            -10.0 * libm::log10f(1.0 + libm::powf(ratio, 2.0 * order as f32))
+        };
+
+        for &order in &[4, 5] {
+            let sections =
+                Coefficients::<f32>::butterworth_lowpass(order as usize, fs, f0).unwrap();
+            assert_eq!(sections.len(), (order as usize).div_ceil(2));
+
+            for &f in &[100.0, 500.0, 1000.0, 2000.0, 5000.0] {
+                let f_hz = Hertz::<f32>::from_hz(f).unwrap();
+                let db: f32 = sections.iter().map(|s| s.magnitude_db(f_hz, fs)).sum();
+                assert!(
+                    (db - expected_db(order, f)).abs() < 0.01,
+                    "{} dB at {} Hz",
+                    db,
+                    f
+                );
+            }
+        }
+
+        assert!(Coefficients::<f32>::butterworth_lowpass(0, fs, f0)
+            .unwrap()
+            .is_empty());
+        assert!(Coefficients::<f32>::butterworth_lowpass(4, fs, 30.khz()).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_butterworth_lowpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let second = Coefficients::<f64>::butterworth_lowpass(2, fs, f0).unwrap();
+        let reference =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert_eq!(second.len(), 1);
+        assert!(second[0].approx_eq(&reference, 1e-6));
+
+        // The bilinear transform of the analog Butterworth response, prewarped at f0
+        let expected_db = |order: i32, f: f64| {
+            let pi = core::f64::consts::PI;
+            let ratio = libm::tan(pi * f / fs.hz()) / libm::tan(pi * f0.hz() / fs.hz());
+            -10.0 * libm::log10(1.0 + libm::pow(ratio, 2.0 * order as f64))
+        };
+
+        for &order in &[4, 5] {
+            let sections =
+                Coefficients::<f64>::butterworth_lowpass(order as usize, fs, f0).unwrap();
+            assert_eq!(sections.len(), (order as usize).div_ceil(2));
+
+            for &f in &[100.0, 500.0, 1000.0, 2000.0, 5000.0] {
+                let f_hz = Hertz::<f64>::from_hz(f).unwrap();
+                let db: f64 = sections.iter().map(|s| s.magnitude_db(f_hz, fs)).sum();
+                assert!(
+                    (db - expected_db(order, f)).abs() < 0.01,
+                    "{} dB at {} Hz",
+                    db,
+                    f
+                );
+            }
+        }
+
+        assert!(Coefficients::<f64>::butterworth_lowpass(0, fs, f0)
+            .unwrap()
+            .is_empty());
+        assert!(Coefficients::<f64>::butterworth_lowpass(4, fs, 30.khz()).is_err());
+    }
+
+    #[test]
+    fn test_hardware_profile_f32() {
+        // Coefficients in Q2.14 truncated towards zero, accumulator truncated to Q8.23
+        struct Mock;
+
+        impl HardwareProfile<f32> for Mock {
+            fn round_coefficient(&self, c: f32) -> f32 {
+                (c * 16384.0) as i16 as f32 / 16384.0
+            }
+
+            fn accumulate(&self, acc: f32) -> f32 {
+                libm::truncf(acc * 8_388_608.0) / 8_388_608.0
+            }
+        }
+
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 200.hz();
+        let reference = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 0.7).unwrap();
+        let profiled =
+            Coefficients::<f32>::from_params_profiled(Type::LowPass, fs, f0, 0.7, &Mock).unwrap();
+
+        // Truncation moves every coefficient towards zero
+        let pairs = [
+            (profiled.a1, reference.a1),
+            (profiled.a2, reference.a2),
+            (profiled.b0, reference.b0),
+            (profiled.b1, reference.b1),
+            (profiled.b2, reference.b2),
+        ];
+        assert!(pairs.iter().all(|&(p, r)| p.abs() <= r.abs()));
+        assert!(pairs.iter().any(|&(p, r)| p != r));
+
+        // Smaller numerator and a less negative a1 lower the DC gain below unity
+        let mut hardware = ProfiledDirectForm1::new(reference, Mock);
+        assert!(hardware.coefficients().approx_eq(&profiled, 0.0));
+        let mut float = DirectForm1::<f32>::new(reference);
+        let (mut y_hw, mut y_float) = (0.0, 0.0);
+        for _ in 0..48_000 {
+            y_hw = hardware.run(1.0);
+            y_float = float.run(1.0);
+        }
+        assert!((y_float - 1.0f32).abs() < 1e-3);
+        assert!(y_hw < y_float - 1e-3);
+    }
+
+    #[test]
+    fn test_hardware_profile_f64() {
+        // Coefficients in Q2.14 truncated towards zero, accumulator truncated to Q8.23
+        struct Mock;
+
+        impl HardwareProfile<f64> for Mock {
+            fn round_coefficient(&self, c: f64) -> f64 {
+                (c * 16384.0) as i16 as f64 / 16384.0
+            }
+
+            fn accumulate(&self, acc: f64) -> f64 {
+                libm::trunc(acc * 8_388_608.0) / 8_388_608.0
+            }
+        }
+
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 200.hz();
+        let reference = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 0.7).unwrap();
+        let profiled =
+            Coefficients::<f64>::from_params_profiled(Type::LowPass, fs, f0, 0.7, &Mock).unwrap();
+
+        // Truncation moves every coefficient towards zero
+        let pairs = [
+            (profiled.a1, reference.a1),
+            (profiled.a2, reference.a2),
+            (profiled.b0, reference.b0),
+            (profiled.b1, reference.b1),
+            (profiled.b2, reference.b2),
+        ];
+        assert!(pairs.iter().all(|&(p, r)| p.abs() <= r.abs()));
+        assert!(pairs.iter().any(|&(p, r)| p != r));
+
+        // Smaller numerator and a less negative a1 lower the DC gain below unity
+        let mut hardware = ProfiledDirectForm1::new(reference, Mock);
+        assert!(hardware.coefficients().approx_eq(&profiled, 0.0));
+        let mut float = DirectForm1::<f64>::new(reference);
+        let (mut y_hw, mut y_float) = (0.0, 0.0);
+        for _ in 0..48_000 {
+            y_hw = hardware.run(1.0);
+            y_float = float.run(1.0);
+        }
+        assert!((y_float - 1.0f64).abs() < 1e-3);
+        assert!(y_hw < y_float - 1e-3);
+    }
+
+    #[test]
+    fn test_phase_response_and_group_delay_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let allpass = Coefficients::<f32>::from_params(Type::AllPass, fs, 2.khz(), 0.7).unwrap();
+
+        for &f in &[50.0, 500.0, 2000.0, 8000.0, 20000.0] {
+            let f = Hertz::<f32>::from_hz(f).unwrap();
+            assert!((allpass.frequency_response(f, fs).0 - 1.0).abs() < 1e-4);
+        }
+        // The phase turns through -pi at the center frequency
+        assert!((allpass.phase_response(2.khz(), fs).abs() - core::f32::consts::PI).abs() < 1e-3);
+        assert!(allpass.phase_response(500.hz(), fs) < -0.1);
+
+        // The analytic group delay matches a central difference of the phase
+        let peaking =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(6.0), fs, 3.khz(), 1.5).unwrap();
+        for &f in &[300.0f32, 1500.0, 3000.0, 6000.0] {
+            let df = 1.0;
+            let (lo, hi) = (
+                Hertz::<f32>::from_hz(f - df).unwrap(),
+                Hertz::<f32>::from_hz(f + df).unwrap(),
+            );
+            let d_omega = 2.0 * core::f32::consts::PI * 2.0 * df / fs.hz();
+            let numeric =
+                -(peaking.phase_response(hi, fs) - peaking.phase_response(lo, fs)) / d_omega;
+            let analytic = peaking.group_delay(Hertz::<f32>::from_hz(f).unwrap(), fs);
+            assert!(
+                (numeric - analytic).abs() < 1e-2,
+                "{} vs {} at {} Hz",
+                numeric,
+                analytic,
+                f
+            );
+        }
+    }
+
+    #[test]
+    fn test_phase_response_and_group_delay_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let allpass = Coefficients::<f64>::from_params(Type::AllPass, fs, 2.khz(), 0.7).unwrap();
+
+        for &f in &[50.0, 500.0, 2000.0, 8000.0, 20000.0] {
+            let f = Hertz::<f64>::from_hz(f).unwrap();
+            assert!((allpass.frequency_response(f, fs).0 - 1.0).abs() < 1e-4);
+        }
+        // The phase turns through -pi at the center frequency
+        assert!((allpass.phase_response(2.khz(), fs).abs() - core::f64::consts::PI).abs() < 1e-3);
+        assert!(allpass.phase_response(500.hz(), fs) < -0.1);
+
+        // The analytic group delay matches a central difference of the phase
+        let peaking =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(6.0), fs, 3.khz(), 1.5).unwrap();
+        for &f in &[300.0f64, 1500.0, 3000.0, 6000.0] {
+            let df = 1.0;
+            let (lo, hi) = (
+                Hertz::<f64>::from_hz(f - df).unwrap(),
+                Hertz::<f64>::from_hz(f + df).unwrap(),
+            );
+            let d_omega = 2.0 * core::f64::consts::PI * 2.0 * df / fs.hz();
+            let numeric =
+                -(peaking.phase_response(hi, fs) - peaking.phase_response(lo, fs)) / d_omega;
+            let analytic = peaking.group_delay(Hertz::<f64>::from_hz(f).unwrap(), fs);
+            assert!(
+                (numeric - analytic).abs() < 1e-2,
+                "{} vs {} at {} Hz",
+                numeric,
+                analytic,
+                f
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_trig_ops() {
+        assert_eq!(Type::<f32>::SinglePoleLowPassApprox.trig_ops(), 0);
+        assert_eq!(Type::<f32>::Through.trig_ops(), 0);
+        assert_eq!(Type::<f32>::SinglePoleLowPass.trig_ops(), 1);
+        assert_eq!(Type::<f32>::LowPass.trig_ops(), 2);
+        assert_eq!(Type::<f32>::HighPass.trig_ops(), 2);
+        assert_eq!(Type::<f32>::BandPass.trig_ops(), 2);
+        assert_eq!(Type::<f32>::Notch.trig_ops(), 2);
+        assert_eq!(Type::<f32>::AllPass.trig_ops(), 2);
+        // The gain conversion adds a pow
+        assert_eq!(Type::LowShelf(3.0f32).trig_ops(), 3);
+        assert_eq!(Type::HighShelf(3.0f64).trig_ops(), 3);
+        assert_eq!(Type::PeakingEQ(3.0f32).trig_ops(), 3);
+    }
+
+    #[test]
+    fn test_fixed_run_i16_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = 0.9 * libm::sinf(2.0 * core::f32::consts::PI * 500.0 * i as f32 / fs.hz());
+            let input = (x * 32768.0) as i16;
+
+            let expected = reference.run(input as f32 / 32768.0) as f64;
+            let output = fixed.run_i16(input) as f64 / 32768.0;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+
+        // Limited by the 16 bit output quantization
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 85.0, "SNR {} dB", snr);
+
+        // The 32 bit path is limited by the precision of the f32 reference instead
+        let mut reference = DirectForm1::<f32>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = 0.9 * libm::sinf(2.0 * core::f32::consts::PI * 500.0 * i as f32 / fs.hz());
+            let input = (x as f64 * 2147483648.0) as i32;
+
+            let expected = reference.run(input as f32 / 2147483648.0) as f64;
+            let output = fixed.run_i32(input) as f64 / 2147483648.0;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 110.0, "SNR {} dB", snr);
+
+        // The overshoot of a full scale step saturates instead of wrapping around
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+        let (min, max) = (0..4800)
+            .map(|_| fixed.run_i32(i32::MAX))
+            .fold((i32::MAX, i32::MIN), |(min, max), y| (min.min(y), max.max(y)));
+        assert!(min >= 0);
+        assert_eq!(max, i32::MAX);
+    }
+
+    #[test]
+    fn test_fixed_run_i16_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = 0.9 * libm::sin(2.0 * core::f64::consts::PI * 500.0 * i as f64 / fs.hz());
+            let input = (x * 32768.0) as i16;
+
+            let expected = reference.run(input as f64 / 32768.0) as f64;
+            let output = fixed.run_i16(input) as f64 / 32768.0;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+
+        // Limited by the 16 bit output quantization
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 85.0, "SNR {} dB", snr);
+
+        // The 32 bit path is limited by the precision of the coefficients instead
+        let mut reference = DirectForm1::<f64>::new(coeffs);
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+        let (mut signal, mut noise) = (0.0f64, 0.0f64);
+        for i in 0..4800 {
+            let x = 0.9 * libm::sin(2.0 * core::f64::consts::PI * 500.0 * i as f64 / fs.hz());
+            let input = (x * 2147483648.0) as i32;
+
+            let expected = reference.run(input as f64 / 2147483648.0) as f64;
+            let output = fixed.run_i32(input) as f64 / 2147483648.0;
+
+            signal += expected * expected;
+            noise += (output - expected) * (output - expected);
+        }
+        let snr = 10.0 * libm::log10(signal / noise);
+        assert!(snr > 150.0, "SNR {} dB", snr);
+
+        // The overshoot of a full scale step saturates instead of wrapping around
+        let mut fixed = DirectForm1Fixed::<30>::new(coeffs.to_fixed::<30>());
+        let (min, max) = (0..4800)
+            .map(|_| fixed.run_i32(i32::MAX))
+            .fold((i32::MAX, i32::MIN), |(min, max), y| (min.min(y), max.max(y)));
+        assert!(min >= 0);
+        assert_eq!(max, i32::MAX);
+    }
+
+    #[test]
+    fn test_constant_q_peaking_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let design = |filter| Coefficients::<f32>::from_params(filter, fs, f0, 1.0).unwrap();
+
+        // The resonance of the cookbook peaking filter narrows with the gain
+        let cookbook_12 = design(Type::PeakingEQ(12.0)).estimate_q().unwrap();
+        let cookbook_3 = design(Type::PeakingEQ(3.0)).estimate_q().unwrap();
+        assert!(cookbook_12 > 1.5 * cookbook_3);
+
+        // While the constant Q variant keeps the same bandwidth
+        let constant_12 = design(Type::ConstantQPeaking(12.0)).estimate_q().unwrap();
+        let constant_3 = design(Type::ConstantQPeaking(3.0)).estimate_q().unwrap();
+        assert!((constant_12 - constant_3).abs() < 1e-3);
+        assert!((constant_12 - 1.0).abs() < 0.05);
+
+        for db_gain in [12.0, 3.0, -6.0] {
+            let coeffs = design(Type::ConstantQPeaking(db_gain));
+            assert!((coeffs.magnitude_db(f0, fs) - db_gain).abs() < 1e-2);
+            assert!(coeffs.magnitude_db(10.hz(), fs).abs() < 1e-2);
+        }
+
+        // A cut is the inverse of the boost
+        let boost = design(Type::ConstantQPeaking(6.0));
+        let cut = design(Type::ConstantQPeaking(-6.0));
+        for f in [100.0, 700.0, 1000.0, 3000.0] {
+            let f: Hertz<f32> = f.hz();
+            assert!((boost.magnitude_db(f, fs) + cut.magnitude_db(f, fs)).abs() < 1e-2);
+        }
+
+        assert!(design(Type::ConstantQPeaking(0.0)).approx_eq(&design(Type::Through), 0.0));
+    }
+
+    #[test]
+    fn test_constant_q_peaking_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let design = |filter| Coefficients::<f64>::from_params(filter, fs, f0, 1.0).unwrap();
+
+        // The resonance of the cookbook peaking filter narrows with the gain
+        let cookbook_12 = design(Type::PeakingEQ(12.0)).estimate_q().unwrap();
+        let cookbook_3 = design(Type::PeakingEQ(3.0)).estimate_q().unwrap();
+        assert!(cookbook_12 > 1.5 * cookbook_3);
+
+        // While the constant Q variant keeps the same bandwidth
+        let constant_12 = design(Type::ConstantQPeaking(12.0)).estimate_q().unwrap();
+        let constant_3 = design(Type::ConstantQPeaking(3.0)).estimate_q().unwrap();
+        assert!((constant_12 - constant_3).abs() < 1e-3);
+        assert!((constant_12 - 1.0).abs() < 0.05);
+
+        for db_gain in [12.0, 3.0, -6.0] {
+            let coeffs = design(Type::ConstantQPeaking(db_gain));
+            assert!((coeffs.magnitude_db(f0, fs) - db_gain).abs() < 1e-2);
+            assert!(coeffs.magnitude_db(10.hz(), fs).abs() < 1e-2);
+        }
+
+        // A cut is the inverse of the boost
+        let boost = design(Type::ConstantQPeaking(6.0));
+        let cut = design(Type::ConstantQPeaking(-6.0));
+        for f in [100.0, 700.0, 1000.0, 3000.0] {
+            let f: Hertz<f64> = f.hz();
+            assert!((boost.magnitude_db(f, fs) + cut.magnitude_db(f, fs)).abs() < 1e-2);
+        }
+
+        assert!(design(Type::ConstantQPeaking(0.0)).approx_eq(&design(Type::Through), 0.0));
+    }
+
+    #[test]
+    fn test_fixed_impulse_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let scale = (1 << 20) as f32;
+
+        // The maximum deviation of the impulse response, relative to the impulse
+        fn impulse_error<const FRAC: u32>(coeffs: Coefficients<f32>, scale: f32) -> f32 {
+            let mut reference = DirectForm1::<f32>::new(coeffs);
+            let mut fixed = DirectForm1Fixed::<FRAC>::new(coeffs.to_fixed::<FRAC>());
+
+            (0..480)
+                .map(|i| {
+                    let input = if i == 0 { scale as i32 } else { 0 };
+                    let expected = reference.run(input as f32);
+                    (fixed.run(input) as f32 - expected).abs() / scale
+                })
+                .fold(0.0, f32::max)
+        }
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let error = impulse_error::<15>(coeffs, scale);
+        assert!(error < 1e-3, "Q1.15 error {}", error);
+
+        // Q1.31 only represents coefficients in [-1, 1)
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 12.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let error = impulse_error::<31>(coeffs, scale);
+        assert!(error < 1e-5, "Q1.31 error {}", error);
+
+        // Overflowing the accumulator saturates instead of wrapping around
+        let coeffs = FixedCoefficients::<0> {
+            a1: 0,
+            a2: 0,
+            b0: i32::MAX,
+            b1: i32::MAX,
+            b2: i32::MAX,
+        };
+        let mut fixed = DirectForm1Fixed::new(coeffs);
+        assert!((0..4).all(|_| fixed.run(i32::MAX) == i32::MAX));
+
+        let mut fixed = DirectForm1Fixed::new(coeffs);
+        assert!((0..4).all(|_| fixed.run(i32::MIN) == i32::MIN));
+    }
+
+    #[test]
+    fn test_fixed_impulse_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let scale = (1 << 20) as f64;
+
+        // The maximum deviation of the impulse response, relative to the impulse
+        fn impulse_error<const FRAC: u32>(coeffs: Coefficients<f64>, scale: f64) -> f64 {
+            let mut reference = DirectForm1::<f64>::new(coeffs);
+            let mut fixed = DirectForm1Fixed::<FRAC>::new(coeffs.to_fixed::<FRAC>());
+
+            (0..480)
+                .map(|i| {
+                    let input = if i == 0 { scale as i32 } else { 0 };
+                    let expected = reference.run(input as f64);
+                    (fixed.run(input) as f64 - expected).abs() / scale
+                })
+                .fold(0.0, f64::max)
+        }
+
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let error = impulse_error::<15>(coeffs, scale);
+        assert!(error < 1e-3, "Q1.15 error {}", error);
+
+        // Q1.31 only represents coefficients in [-1, 1)
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 12.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let error = impulse_error::<31>(coeffs, scale);
+        assert!(error < 1e-5, "Q1.31 error {}", error);
+
+        // Overflowing the accumulator saturates instead of wrapping around
+        let coeffs = FixedCoefficients::<0> {
+            a1: 0,
+            a2: 0,
+            b0: i32::MAX,
+            b1: i32::MAX,
+            b2: i32::MAX,
+        };
+        let mut fixed = DirectForm1Fixed::new(coeffs);
+        assert!((0..4).all(|_| fixed.run(i32::MAX) == i32::MAX));
+
+        let mut fixed = DirectForm1Fixed::new(coeffs);
+        assert!((0..4).all(|_| fixed.run(i32::MIN) == i32::MIN));
+    }
+
+    #[test]
+    fn test_is_minimum_phase_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        // Both zeros at z = -1, on the unit circle
+        let lowpass =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(!lowpass.is_minimum_phase());
+
+        // A cut with a peaking filter has its zeros inside the unit circle
+        let peaking = Coefficients::<f32>::from_params(Type::PeakingEQ(-6.0), fs, f0, 1.0).unwrap();
+        assert!(peaking.is_minimum_phase());
+
+        // A zero at z = 2
+        let outside = Coefficients::<f32> {
+            b0: 1.0,
+            b1: -2.0,
+            b2: 0.0,
+            ..lowpass
+        };
+        assert!(!outside.is_minimum_phase());
+
+        // The all pass mirrors its poles to zeros outside the unit circle
+        let allpass =
+            Coefficients::<f32>::from_params(Type::AllPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(!allpass.is_minimum_phase());
+
+        let delayed = Coefficients::<f32> { b0: 0.0, ..peaking };
+        assert!(!delayed.is_minimum_phase());
+    }
+
+    #[test]
+    fn test_is_minimum_phase_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        // Both zeros at z = -1, on the unit circle
+        let lowpass =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(!lowpass.is_minimum_phase());
+
+        // A cut with a peaking filter has its zeros inside the unit circle
+        let peaking = Coefficients::<f64>::from_params(Type::PeakingEQ(-6.0), fs, f0, 1.0).unwrap();
+        assert!(peaking.is_minimum_phase());
+
+        // A zero at z = 2
+        let outside = Coefficients::<f64> {
+            b0: 1.0,
+            b1: -2.0,
+            b2: 0.0,
+            ..lowpass
+        };
+        assert!(!outside.is_minimum_phase());
+
+        // The all pass mirrors its poles to zeros outside the unit circle
+        let allpass =
+            Coefficients::<f64>::from_params(Type::AllPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(!allpass.is_minimum_phase());
+
+        let delayed = Coefficients::<f64> { b0: 0.0, ..peaking };
+        assert!(!delayed.is_minimum_phase());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_impulse_step_response_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::LowPass, fs, f0, 2.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+        biquad.run(0.5);
+        let mut state = biquad;
+
+        let impulse = biquad.impulse_response(256);
+        let step = biquad.step_response(256);
+        assert_eq!(impulse.len(), 256);
+        assert_eq!(impulse[0], coeffs.b0);
+
+        // The step response is the cumulative sum of the impulse response
+        let mut sum = 0.0;
+        for (h, s) in impulse.iter().zip(&step) {
+            sum += h;
+            assert!((sum - s).abs() < 1e-5);
+        }
+
+        // The state of the filter is left untouched
+        assert_eq!(biquad.run(0.0), state.run(0.0));
+        assert!(biquad.impulse_response(0).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_impulse_step_response_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::LowPass, fs, f0, 2.0).unwrap();
+        let mut biquad = DirectForm2Transposed::<f64>::new(coeffs);
+        biquad.run(0.5);
+        let mut state = biquad;
+
+        let impulse = biquad.impulse_response(256);
+        let step = biquad.step_response(256);
+        assert_eq!(impulse.len(), 256);
+        assert_eq!(impulse[0], coeffs.b0);
+
+        // The step response is the cumulative sum of the impulse response
+        let mut sum = 0.0;
+        for (h, s) in impulse.iter().zip(&step) {
+            sum += h;
+            assert!((sum - s).abs() < 1e-5);
+        }
+
+        // The state of the filter is left untouched
+        assert_eq!(biquad.run(0.0), state.run(0.0));
+        assert!(biquad.impulse_response(0).is_empty());
+    }
+
+    #[test]
+    fn test_cascade_run_solo_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let band = |f0: f32, db_gain: f32| {
+            Coefficients::<f32>::from_params(Type::PeakingEQ(db_gain), fs, f0.hz(), 1.0).unwrap()
+        };
+        let bands = [band(100.0, 6.0), band(1_000.0, -3.0), band(5_000.0, 9.0)];
+        let mut eq = Cascade::<DirectForm1<f32>, 3>::from_coefficients(bands);
+        let mut single = DirectForm1::<f32>::new(bands[1]);
+
+        for n in 0..64 {
+            let input = if n % 5 == 0 { 1.0 } else { -0.3 };
+            assert_eq!(eq.run_solo(input, 1), single.run(input));
+        }
+
+        // The bypassed bands are not run
+        let mut untouched = Cascade::<DirectForm1<f32>, 3>::from_coefficients(bands);
+        for (stage, other) in eq
+            .stages_mut()
+            .iter_mut()
+            .zip(untouched.stages_mut())
+            .step_by(2)
+        {
+            assert_eq!(stage.run(1.0), other.run(1.0));
+        }
+    }
+
+    #[test]
+    fn test_cascade_run_solo_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let band = |f0: f64, db_gain: f64| {
+            Coefficients::<f64>::from_params(Type::PeakingEQ(db_gain), fs, f0.hz(), 1.0).unwrap()
+        };
+        let bands = [band(100.0, 6.0), band(1_000.0, -3.0), band(5_000.0, 9.0)];
+        let mut eq = Cascade::<DirectForm1<f64>, 3>::from_coefficients(bands);
+        let mut single = DirectForm1::<f64>::new(bands[1]);
+
+        for n in 0..64 {
+            let input = if n % 5 == 0 { 1.0 } else { -0.3 };
+            assert_eq!(eq.run_solo(input, 1), single.run(input));
+        }
+
+        // The bypassed bands are not run
+        let mut untouched = Cascade::<DirectForm1<f64>, 3>::from_coefficients(bands);
+        for (stage, other) in eq
+            .stages_mut()
+            .iter_mut()
+            .zip(untouched.stages_mut())
+            .step_by(2)
+        {
+            assert_eq!(stage.run(1.0), other.run(1.0));
+        }
+    }
+
+    #[test]
+    fn test_effective_cutoff_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let design = |filter, f0: f32, q: f32| {
+            Coefficients::<f32>::from_params(filter, fs, f0.hz(), q).unwrap()
+        };
+
+        // The prewarped Butterworth designs are exact at any ratio
+        for &f0 in &[1_000.0, 15_000.0] {
+            let lowpass = design(Type::LowPass, f0, Q_BUTTERWORTH_F32);
+            let cutoff = lowpass.effective_cutoff(fs).unwrap().hz();
+            assert!((cutoff - f0).abs() < 1e-3 * f0, "{} {}", f0, cutoff);
+
+            let highpass = design(Type::HighPass, f0, Q_BUTTERWORTH_F32);
+            let cutoff = highpass.effective_cutoff(fs).unwrap().hz();
+            assert!((cutoff - f0).abs() < 1e-3 * f0, "{} {}", f0, cutoff);
+        }
+
+        // With Q = 1 the analog -3 dB point is at 1.272 f0, which the warping moves for high ratios
+        let analog = libm::sqrtf(0.5 * (1.0 + libm::sqrtf(5.0)));
+        let low_ratio = design(Type::LowPass, 100.0, 1.0)
+            .effective_cutoff(fs)
+            .unwrap()
+            .hz();
+        assert!((low_ratio / 100.0 - analog).abs() < 1e-3, "{}", low_ratio);
+        let high_ratio = design(Type::LowPass, 12_000.0, 1.0)
+            .effective_cutoff(fs)
+            .unwrap()
+            .hz();
+        assert!(
+            (high_ratio / 12_000.0 - analog).abs() > 0.05,
+            "{}",
+            high_ratio
+        );
+
+        assert!(design(Type::AllPass, 1_000.0, 1.0)
+            .effective_cutoff(fs)
+            .is_none());
+    }
+
+    #[test]
+    fn test_effective_cutoff_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let design = |filter, f0: f64, q: f64| {
+            Coefficients::<f64>::from_params(filter, fs, f0.hz(), q).unwrap()
+        };
+
+        // The prewarped Butterworth designs are exact at any ratio
+        for &f0 in &[1_000.0, 15_000.0] {
+            let lowpass = design(Type::LowPass, f0, Q_BUTTERWORTH_F64);
+            let cutoff = lowpass.effective_cutoff(fs).unwrap().hz();
+            assert!((cutoff - f0).abs() < 1e-3 * f0, "{} {}", f0, cutoff);
+
+            let highpass = design(Type::HighPass, f0, Q_BUTTERWORTH_F64);
+            let cutoff = highpass.effective_cutoff(fs).unwrap().hz();
+            assert!((cutoff - f0).abs() < 1e-3 * f0, "{} {}", f0, cutoff);
+        }
+
+        // With Q = 1 the analog -3 dB point is at 1.272 f0, which the warping moves for high ratios
+        let analog = libm::sqrt(0.5 * (1.0 + libm::sqrt(5.0)));
+        let low_ratio = design(Type::LowPass, 100.0, 1.0)
+            .effective_cutoff(fs)
+            .unwrap()
+            .hz();
+        assert!((low_ratio / 100.0 - analog).abs() < 1e-3, "{}", low_ratio);
+        let high_ratio = design(Type::LowPass, 12_000.0, 1.0)
+            .effective_cutoff(fs)
+            .unwrap()
+            .hz();
+        assert!(
+            (high_ratio / 12_000.0 - analog).abs() > 0.05,
+            "{}",
+            high_ratio
+        );
+
+        assert!(design(Type::AllPass, 1_000.0, 1.0)
+            .effective_cutoff(fs)
+            .is_none());
+    }
+
+    #[test]
+    fn test_gain_at_dc_nyquist_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let lowpass =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!((lowpass.gain_at_dc() - 1.0).abs() < 1e-4);
+        assert!(lowpass.gain_at_nyquist().abs() < 1e-4);
+
+        let highpass =
+            Coefficients::<f32>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(highpass.gain_at_dc().abs() < 1e-4);
+        assert!((highpass.gain_at_nyquist() - 1.0).abs() < 1e-4);
+
+        // Matches the full complex evaluation
+        let shelf = Coefficients::<f32>::from_params(Type::LowShelf(6.0), fs, f0, 0.7).unwrap();
+        let (dc, _) = shelf.frequency_response(1e-3.hz(), fs);
+        let (nyquist, _) = shelf.frequency_response(24.khz(), fs);
+        assert!((shelf.gain_at_dc() - dc).abs() < 1e-4);
+        assert!((shelf.gain_at_nyquist() - nyquist).abs() < 1e-4);
+
+        // The phase of the all pass turns by a full cycle between DC and Nyquist
+        let allpass = Coefficients::<f32>::from_params(Type::AllPass, fs, f0, 1.0).unwrap();
+        assert!((allpass.gain_at_dc() - 1.0).abs() < 1e-4);
+        assert!((allpass.gain_at_nyquist() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gain_at_dc_nyquist_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let lowpass =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!((lowpass.gain_at_dc() - 1.0).abs() < 1e-4);
+        assert!(lowpass.gain_at_nyquist().abs() < 1e-4);
+
+        let highpass =
+            Coefficients::<f64>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(highpass.gain_at_dc().abs() < 1e-4);
+        assert!((highpass.gain_at_nyquist() - 1.0).abs() < 1e-4);
+
+        // Matches the full complex evaluation
+        let shelf = Coefficients::<f64>::from_params(Type::LowShelf(6.0), fs, f0, 0.7).unwrap();
+        let (dc, _) = shelf.frequency_response(1e-3.hz(), fs);
+        let (nyquist, _) = shelf.frequency_response(24.khz(), fs);
+        assert!((shelf.gain_at_dc() - dc).abs() < 1e-4);
+        assert!((shelf.gain_at_nyquist() - nyquist).abs() < 1e-4);
+
+        // The phase of the all pass turns by a full cycle between DC and Nyquist
+        let allpass = Coefficients::<f64>::from_params(Type::AllPass, fs, f0, 1.0).unwrap();
+        assert!((allpass.gain_at_dc() - 1.0).abs() < 1e-4);
+        assert!((allpass.gain_at_nyquist() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_coefficients_builder_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let builder = CoefficientsBuilder::<f32>::new()
+            .filter_type(Type::LowPass)
+            .sample_rate(fs)
+            .cutoff(f0);
+
+        // The Q value defaults to Butterworth
+        let expected =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(builder.build().unwrap().approx_eq(&expected, 0.0));
+
+        // The gain overrides the gain of the filter type
+        let peaking = builder
+            .filter_type(Type::PeakingEQ(3.0))
+            .q(2.0)
+            .gain_db(-6.0)
+            .build()
+            .unwrap();
+        let expected =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(-6.0), fs, f0, 2.0).unwrap();
+        assert!(peaking.approx_eq(&expected, 0.0));
+
+        // Errors of the parameters are passed on
+        assert_eq!(builder.cutoff(30.khz()).build().err(), Some(Errors::OutsideNyquist));
+
+        let missing = [
+            CoefficientsBuilder::<f32>::new().sample_rate(fs).cutoff(f0),
+            CoefficientsBuilder::<f32>::new()
+                .filter_type(Type::LowPass)
+                .cutoff(f0),
+            CoefficientsBuilder::<f32>::new()
+                .filter_type(Type::LowPass)
+                .sample_rate(fs),
+        ];
+        for builder in &missing {
+            assert_eq!(builder.q(1.0).build().err(), Some(Errors::MissingParameter));
+        }
+    }
+
+    #[test]
+    fn test_coefficients_builder_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let builder = CoefficientsBuilder::<f64>::new()
+            .filter_type(Type::LowPass)
+            .sample_rate(fs)
+            .cutoff(f0);
+
+        // The Q value defaults to Butterworth
+        let expected =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(builder.build().unwrap().approx_eq(&expected, 0.0));
+
+        // The gain overrides the gain of the filter type
+        let peaking = builder
+            .filter_type(Type::PeakingEQ(3.0))
+            .q(2.0)
+            .gain_db(-6.0)
+            .build()
+            .unwrap();
+        let expected =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(-6.0), fs, f0, 2.0).unwrap();
+        assert!(peaking.approx_eq(&expected, 0.0));
+
+        // Errors of the parameters are passed on
+        assert_eq!(builder.cutoff(30.khz()).build().err(), Some(Errors::OutsideNyquist));
+
+        let missing = [
+            CoefficientsBuilder::<f64>::new().sample_rate(fs).cutoff(f0),
+            CoefficientsBuilder::<f64>::new()
+                .filter_type(Type::LowPass)
+                .cutoff(f0),
+            CoefficientsBuilder::<f64>::new()
+                .filter_type(Type::LowPass)
+                .sample_rate(fs),
+        ];
+        for builder in &missing {
+            assert_eq!(builder.q(1.0).build().err(), Some(Errors::MissingParameter));
+        }
+    }
+
+    #[test]
+    fn test_decibels_f32() {
+        assert!((Decibels(6.0f32).to_linear() - 1.995).abs() < 1e-3);
+        assert!((Decibels::<f32>::from_linear(0.5).db() + 6.0206).abs() < 1e-3);
+        assert!(
+            (Decibels::<f32>::from_linear(Decibels(-3.0f32).to_linear()).db() + 3.0).abs() < 1e-5
+        );
+        assert_eq!(Decibels::from(6.0f32), Decibels(6.0));
+
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let typed =
+            Coefficients::<f32>::from_params(Type::PeakingEQ(Decibels(6.0)).into(), fs, f0, 1.0)
+                .unwrap();
+        let plain = Coefficients::<f32>::from_params(Type::PeakingEQ(6.0), fs, f0, 1.0).unwrap();
+        assert!(typed.approx_eq(&plain, 0.0));
+        assert!((typed.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+
+        // The gain taking constructors accept both
+        assert_eq!(
+            Coefficients::<f32>::high_shelf_maxflat(fs, f0, Decibels(6.0)),
+            Coefficients::<f32>::high_shelf_maxflat(fs, f0, 6.0)
+        );
+        assert_eq!(
+            Coefficients::<f32>::band_pass_peak_db(fs, f0, 2.0, Decibels(-3.0)),
+            Coefficients::<f32>::band_pass_peak_db(fs, f0, 2.0, -3.0)
+        );
+        assert_eq!(
+            Coefficients::<f32>::peaking_gain_at(fs, f0, 1.0, 1.2.khz(), Decibels(3.0)),
+            Coefficients::<f32>::peaking_gain_at(fs, f0, 1.0, 1.2.khz(), 3.0)
+        );
+        assert_eq!(
+            Coefficients::<f32>::from_params_normalized(
+                Type::PeakingEQ(Decibels(6.0)).into(),
+                fs,
+                f0,
+                1.0
+            ),
+            Coefficients::<f32>::from_params_normalized(Type::PeakingEQ(6.0), fs, f0, 1.0)
+        );
+        let typed = Cascade::<DirectForm1<f32>, 2>::loudness_compensation(fs, Decibels(60.0));
+        let plain = Cascade::<DirectForm1<f32>, 2>::loudness_compensation(fs, 60.0);
+        let coefficients = |cascade: Cascade<DirectForm1<f32>, 2>| {
+            cascade.stages().map(|stage| stage.coefficients())
+        };
+        assert_eq!(typed.map(coefficients), plain.map(coefficients));
+    }
+
+    #[test]
+    fn test_decibels_f64() {
+        assert!((Decibels(6.0f64).to_linear() - 1.995).abs() < 1e-3);
+        assert!((Decibels::<f64>::from_linear(0.5).db() + 6.0206).abs() < 1e-3);
+        assert!(
+            (Decibels::<f64>::from_linear(Decibels(-3.0f64).to_linear()).db() + 3.0).abs() < 1e-5
+        );
+        assert_eq!(Decibels::from(6.0f64), Decibels(6.0));
+
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let typed =
+            Coefficients::<f64>::from_params(Type::PeakingEQ(Decibels(6.0)).into(), fs, f0, 1.0)
+                .unwrap();
+        let plain = Coefficients::<f64>::from_params(Type::PeakingEQ(6.0), fs, f0, 1.0).unwrap();
+        assert!(typed.approx_eq(&plain, 0.0));
+        assert!((typed.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+
+        // The gain taking constructors accept both
+        assert_eq!(
+            Coefficients::<f64>::high_shelf_maxflat(fs, f0, Decibels(6.0)),
+            Coefficients::<f64>::high_shelf_maxflat(fs, f0, 6.0)
+        );
+        assert_eq!(
+            Coefficients::<f64>::band_pass_peak_db(fs, f0, 2.0, Decibels(-3.0)),
+            Coefficients::<f64>::band_pass_peak_db(fs, f0, 2.0, -3.0)
+        );
+        assert_eq!(
+            Coefficients::<f64>::peaking_gain_at(fs, f0, 1.0, 1.2.khz(), Decibels(3.0)),
+            Coefficients::<f64>::peaking_gain_at(fs, f0, 1.0, 1.2.khz(), 3.0)
+        );
+        assert_eq!(
+            Coefficients::<f64>::from_params_normalized(
+                Type::PeakingEQ(Decibels(6.0)).into(),
+                fs,
+                f0,
+                1.0
+            ),
+            Coefficients::<f64>::from_params_normalized(Type::PeakingEQ(6.0), fs, f0, 1.0)
+        );
+        let typed = Cascade::<DirectForm1<f64>, 2>::loudness_compensation(fs, Decibels(60.0));
+        let plain = Cascade::<DirectForm1<f64>, 2>::loudness_compensation(fs, 60.0);
+        let coefficients = |cascade: Cascade<DirectForm1<f64>, 2>| {
+            cascade.stages().map(|stage| stage.coefficients())
+        };
+        assert_eq!(typed.map(coefficients), plain.map(coefficients));
+    }
+
+    #[test]
+    fn test_coefficients_eq_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert_eq!(coeffs, coeffs);
+        assert_eq!(
+            Coefficients::<f32>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32),
+            Ok(coeffs)
+        );
+
+        let nudged = Coefficients::<f32> {
+            b0: coeffs.b0 + 1e-6,
+            ..coeffs
+        };
+        assert_ne!(coeffs, nudged);
+        assert!(coeffs.approx_eq(&nudged, 1e-5));
+        assert!(!coeffs.approx_eq(&nudged, 1e-7));
+
+        // The design in the precision of the samples converges to the one in f64
+        let generic =
+            Coefficients::<f32>::from_params_fast(Type::LowPass, fs, f0, Q_BUTTERWORTH_F32)
+                .unwrap();
+        assert!(coeffs.approx_eq(&generic, 1e-6));
+
+        let nan = Coefficients::<f32> {
+            a2: f32::NAN,
+            ..coeffs
+        };
+        assert_ne!(nan, nan);
+        assert!(!nan.approx_eq(&nan, f32::INFINITY));
+    }
+
+    #[test]
+    fn test_coefficients_eq_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert_eq!(coeffs, coeffs);
+        assert_eq!(
+            Coefficients::<f64>::from_params(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64),
+            Ok(coeffs)
+        );
+
+        let nudged = Coefficients::<f64> {
+            b0: coeffs.b0 + 1e-6,
+            ..coeffs
+        };
+        assert_ne!(coeffs, nudged);
+        assert!(coeffs.approx_eq(&nudged, 1e-5));
+        assert!(!coeffs.approx_eq(&nudged, 1e-7));
+
+        // The design in the precision of the samples converges to the one in f64
+        let generic =
+            Coefficients::<f64>::from_params_fast(Type::LowPass, fs, f0, Q_BUTTERWORTH_F64)
+                .unwrap();
+        assert!(coeffs.approx_eq(&generic, 1e-6));
+
+        let nan = Coefficients::<f64> {
+            a2: f64::NAN,
+            ..coeffs
+        };
+        assert_ne!(nan, nan);
+        assert!(!nan.approx_eq(&nan, f64::INFINITY));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_filter_stream() {
+        use std::io::Read;
+
+        // A reader returning at most 3 bytes per read, splitting the samples
+        struct Trickle<'a>(&'a [u8]);
+
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(self.0.len()).min(3);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let input: Vec<f32> = (0..5000)
+            .map(|i| 0.5 * libm::sinf(0.01 * i as f32) + if i % 7 == 0 { 0.4 } else { 0.0 })
+            .collect();
+        let mut expected = vec![0.0; input.len()];
+        DirectForm2Transposed::<f32>::new(coeffs).process_block(&input, &mut expected);
+
+        let bytes: Vec<u8> = input.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let mut output = Vec::new();
+        filter_stream(coeffs, Trickle(&bytes), &mut output, PcmFormat::F32Le).unwrap();
+        let output: Vec<f32> = output
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        assert_eq!(output, expected);
+
+        let bytes: Vec<u8> = input
+            .iter()
+            .flat_map(|x| ((x * 32768.0) as i16).to_le_bytes())
+            .collect();
+        let mut output = Vec::new();
+        filter_stream(coeffs, Trickle(&bytes), &mut output, PcmFormat::I16Le).unwrap();
+        assert_eq!(output.len(), bytes.len());
+        for (b, y) in output.chunks_exact(2).zip(&expected) {
+            let sample = i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0;
+            assert!((sample - y).abs() < 1e-3);
+        }
+
+        // A partial sample at the end of the stream
+        let error = filter_stream(coeffs, &bytes[..3], &mut Vec::new(), PcmFormat::I16Le);
+        assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_retunable_set_q_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let mut band = RetunableFilter::<f32>::new(Type::PeakingEQ(6.0), fs, f0, 1.0).unwrap();
+        let mut reference = DirectForm1::<f32>::new(band.coefficients());
+
+        // The half gain bandwidth, from the width of the response above 3 dB
+        let bandwidth = |coeffs: Coefficients<f32>| {
+            (1..2000)
+                .filter(|&i| coeffs.magnitude_db((i as f32 * 10.0).hz(), fs) > 3.0)
+                .count()
+        };
+        let wide = band.coefficients();
+
+        for i in 0..100 {
+            let input = if i % 9 == 0 { 1.0 } else { -0.2 };
+            assert_eq!(band.run(input), reference.run(input));
+        }
+        band.set_q(4.0).unwrap();
+        assert_eq!(band.q(), 4.0);
+        let narrow = band.coefficients();
+
+        assert!(bandwidth(narrow) * 3 < bandwidth(wide));
+        assert!((narrow.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+        assert!((wide.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+
+        // The state is kept across the change
+        reference.update_coefficients(narrow);
+        for i in 0..100 {
+            let input = if i % 9 == 0 { 1.0 } else { -0.2 };
+            assert_eq!(band.run(input), reference.run(input));
+        }
+
+        assert_eq!(band.set_q(-1.0), Err(Errors::NegativeQ));
+        assert_eq!(band.q(), 4.0);
+        assert_eq!(band.coefficients(), narrow);
+    }
+
+    #[test]
+    fn test_retunable_set_q_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let mut band = RetunableFilter::<f64>::new(Type::PeakingEQ(6.0), fs, f0, 1.0).unwrap();
+        let mut reference = DirectForm1::<f64>::new(band.coefficients());
+
+        // The half gain bandwidth, from the width of the response above 3 dB
+        let bandwidth = |coeffs: Coefficients<f64>| {
+            (1..2000)
+                .filter(|&i| coeffs.magnitude_db((i as f64 * 10.0).hz(), fs) > 3.0)
+                .count()
+        };
+        let wide = band.coefficients();
+
+        for i in 0..100 {
+            let input = if i % 9 == 0 { 1.0 } else { -0.2 };
+            assert_eq!(band.run(input), reference.run(input));
+        }
+        band.set_q(4.0).unwrap();
+        assert_eq!(band.q(), 4.0);
+        let narrow = band.coefficients();
+
+        assert!(bandwidth(narrow) * 3 < bandwidth(wide));
+        assert!((narrow.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+        assert!((wide.magnitude_db(f0, fs) - 6.0).abs() < 1e-3);
+
+        // The state is kept across the change
+        reference.update_coefficients(narrow);
+        for i in 0..100 {
+            let input = if i % 9 == 0 { 1.0 } else { -0.2 };
+            assert_eq!(band.run(input), reference.run(input));
+        }
+
+        assert_eq!(band.set_q(-1.0), Err(Errors::NegativeQ));
+        assert_eq!(band.q(), 4.0);
+        assert_eq!(band.coefficients(), narrow);
+    }
+
+    #[test]
+    fn test_filter_state_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::BandPass, fs, 2.khz(), 5.0).unwrap();
+        let input = |i: i32| if i % 13 == 0 { 1.0 } else { -0.1 };
+
+        let mut uninterrupted = DirectForm1::<f32>::new(coeffs);
+        let mut paused = DirectForm1::<f32>::new(coeffs);
+        for i in 0..100 {
+            assert_eq!(paused.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        // Resuming in a fresh filter continues without a discontinuity
+        let state = paused.get_state();
+        let mut resumed = DirectForm1::<f32>::new(coeffs);
+        resumed.set_state(state);
+        assert_eq!(resumed.get_state(), state);
+        for i in 100..200 {
+            assert_eq!(resumed.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        resumed.reset_state();
+        assert_eq!(
+            resumed.get_state(),
+            FilterState {
+                x1: 0.0,
+                x2: 0.0,
+                y1: 0.0,
+                y2: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_filter_state_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::BandPass, fs, 2.khz(), 5.0).unwrap();
+        let input = |i: i32| if i % 13 == 0 { 1.0 } else { -0.1 };
+
+        let mut uninterrupted = DirectForm1::<f64>::new(coeffs);
+        let mut paused = DirectForm1::<f64>::new(coeffs);
+        for i in 0..100 {
+            assert_eq!(paused.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        // Resuming in a fresh filter continues without a discontinuity
+        let state = paused.get_state();
+        let mut resumed = DirectForm1::<f64>::new(coeffs);
+        resumed.set_state(state);
+        assert_eq!(resumed.get_state(), state);
+        for i in 100..200 {
+            assert_eq!(resumed.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        resumed.reset_state();
+        assert_eq!(
+            resumed.get_state(),
+            FilterState {
+                x1: 0.0,
+                x2: 0.0,
+                y1: 0.0,
+                y2: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_transposed_state_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let coeffs = Coefficients::<f32>::from_params(Type::BandPass, fs, 2.khz(), 5.0).unwrap();
+        let input = |i: i32| if i % 13 == 0 { 1.0 } else { -0.1 };
+
+        let mut uninterrupted = DirectForm2Transposed::<f32>::new(coeffs);
+        let mut paused = DirectForm2Transposed::<f32>::new(coeffs);
+        for i in 0..100 {
+            assert_eq!(paused.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        // Resuming in a fresh filter continues without a discontinuity
+        let state = paused.get_state();
+        let mut resumed = DirectForm2Transposed::<f32>::new(coeffs);
+        resumed.set_state(state);
+        assert_eq!(resumed.get_state(), state);
+        for i in 100..200 {
+            assert_eq!(resumed.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        resumed.reset_state();
+        assert_eq!(resumed.get_state(), TransposedState { s1: 0.0, s2: 0.0 });
+    }
+
+    #[test]
+    fn test_transposed_state_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let coeffs = Coefficients::<f64>::from_params(Type::BandPass, fs, 2.khz(), 5.0).unwrap();
+        let input = |i: i32| if i % 13 == 0 { 1.0 } else { -0.1 };
+
+        let mut uninterrupted = DirectForm2Transposed::<f64>::new(coeffs);
+        let mut paused = DirectForm2Transposed::<f64>::new(coeffs);
+        for i in 0..100 {
+            assert_eq!(paused.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        // Resuming in a fresh filter continues without a discontinuity
+        let state = paused.get_state();
+        let mut resumed = DirectForm2Transposed::<f64>::new(coeffs);
+        resumed.set_state(state);
+        assert_eq!(resumed.get_state(), state);
+        for i in 100..200 {
+            assert_eq!(resumed.run(input(i)), uninterrupted.run(input(i)));
+        }
+
+        resumed.reset_state();
+        assert_eq!(resumed.get_state(), TransposedState { s1: 0.0, s2: 0.0 });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_bessel_lowpass_sections_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let second = Coefficients::<f32>::bessel_lowpass_sections(2, fs, f0).unwrap();
+        assert_eq!(second.len(), 1);
+        assert!((second[0].estimate_q().unwrap() - Q_BESSEL_F32).abs() < 1e-3);
+
+        let response = |sections: &[Coefficients<f32>], f: f32| {
+            let f = Hertz::<f32>::from_hz(f).unwrap();
+            let db: f32 = sections.iter().map(|s| s.magnitude_db(f, fs)).sum();
+            let delay: f32 = sections.iter().map(|s| s.group_delay(f, fs)).sum();
+            (db, delay)
+        };
+
+        for order in 1..=8 {
+            let bessel = Coefficients::<f32>::bessel_lowpass_sections(order, fs, f0).unwrap();
+            assert_eq!(bessel.len(), order.div_ceil(2));
+
+            // The cutoff is at the -3 dB point
+            let (db, _) = response(&bessel, f0.hz());
+            assert!((db + 3.0103).abs() < 0.01, "order {}: {} dB", order, db);
+
+            // The group delay varies much less below half the cutoff than for a Butterworth
+            let butterworth = Coefficients::<f32>::butterworth_lowpass(order, fs, f0).unwrap();
+            let variation = |sections: &[Coefficients<f32>]| {
+                let delays: Vec<f32> = (1..=10)
+                    .map(|i| response(sections, i as f32 * 50.0).1)
+                    .collect();
+                let max = delays.iter().cloned().fold(f32::MIN, f32::max);
+                let min = delays.iter().cloned().fold(f32::MAX, f32::min);
+                max - min
+            };
+            if order >= 2 {
+                assert!(
+                    variation(&bessel) * 5.0 < variation(&butterworth),
+                    "order {}: {} and {}",
+                    order,
+                    variation(&bessel),
+                    variation(&butterworth)
+                );
+            }
+        }
+
+        assert!(Coefficients::<f32>::bessel_lowpass_sections(0, fs, f0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f32>::bessel_lowpass_sections(9, fs, f0),
+            Err(Errors::OrderTooHigh)
+        );
+        assert_eq!(
+            Coefficients::<f32>::bessel_lowpass_sections(4, fs, 30.khz()),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_bessel_lowpass_sections_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let second = Coefficients::<f64>::bessel_lowpass_sections(2, fs, f0).unwrap();
+        assert_eq!(second.len(), 1);
+        assert!((second[0].estimate_q().unwrap() - Q_BESSEL_F64).abs() < 1e-3);
+
+        let response = |sections: &[Coefficients<f64>], f: f64| {
+            let f = Hertz::<f64>::from_hz(f).unwrap();
+            let db: f64 = sections.iter().map(|s| s.magnitude_db(f, fs)).sum();
+            let delay: f64 = sections.iter().map(|s| s.group_delay(f, fs)).sum();
+            (db, delay)
+        };
+
+        for order in 1..=8 {
+            let bessel = Coefficients::<f64>::bessel_lowpass_sections(order, fs, f0).unwrap();
+            assert_eq!(bessel.len(), order.div_ceil(2));
+
+            // The cutoff is at the -3 dB point
+            let (db, _) = response(&bessel, f0.hz());
+            assert!((db + 3.0103).abs() < 0.01, "order {}: {} dB", order, db);
+
+            // The group delay varies much less below half the cutoff than for a Butterworth
+            let butterworth = Coefficients::<f64>::butterworth_lowpass(order, fs, f0).unwrap();
+            let variation = |sections: &[Coefficients<f64>]| {
+                let delays: Vec<f64> = (1..=10)
+                    .map(|i| response(sections, i as f64 * 50.0).1)
+                    .collect();
+                let max = delays.iter().cloned().fold(f64::MIN, f64::max);
+                let min = delays.iter().cloned().fold(f64::MAX, f64::min);
+                max - min
+            };
+            if order >= 2 {
+                assert!(
+                    variation(&bessel) * 5.0 < variation(&butterworth),
+                    "order {}: {} and {}",
+                    order,
+                    variation(&bessel),
+                    variation(&butterworth)
+                );
+            }
+        }
+
+        assert!(Coefficients::<f64>::bessel_lowpass_sections(0, fs, f0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f64>::bessel_lowpass_sections(9, fs, f0),
+            Err(Errors::OrderTooHigh)
+        );
+        assert_eq!(
+            Coefficients::<f64>::bessel_lowpass_sections(4, fs, 30.khz()),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[test]
+    fn test_cascade_total_latency_samples_f32() {
+        // A stage with the latency of e.g. an oversampled or lookahead filter
+        #[derive(Copy, Clone)]
+        struct Delayed {
+            filter: DirectForm1<f32>,
+            latency: usize,
+        }
+
+        impl Biquad<f32> for Delayed {
+            fn run(&mut self, input: f32) -> f32 {
+                self.filter.run(input)
+            }
+
+            fn update_coefficients(&mut self, new_coefficients: Coefficients<f32>) {
+                self.filter.update_coefficients(new_coefficients);
+            }
+
+            fn replace_coefficients(
+                &mut self,
+                new_coefficients: Coefficients<f32>,
+            ) -> Coefficients<f32> {
+                self.filter.replace_coefficients(new_coefficients)
+            }
+
+            fn reset_state(&mut self) {
+                self.filter.reset_state();
+            }
+
+            fn latency_samples(&self) -> usize {
+                self.latency
+            }
+        }
+
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+        let filter = DirectForm1::<f32>::new(coeffs);
+
+        let plain = Cascade::new([filter, filter]);
+        assert_eq!(plain.total_latency_samples(), 0);
+
+        let mixed = Cascade::new([
+            Delayed { filter, latency: 0 },
+            Delayed {
+                filter,
+                latency: 32,
+            },
+        ]);
+        assert_eq!(mixed.total_latency_samples(), 32);
+
+        let oversampled = Cascade::new(
+            [Delayed {
+                filter,
+                latency: 32,
+            }; 3],
+        );
+        assert_eq!(oversampled.total_latency_samples(), 96);
+    }
+
+    #[test]
+    fn test_cascade_total_latency_samples_f64() {
+        // A stage with the latency of e.g. an oversampled or lookahead filter
+        #[derive(Copy, Clone)]
+        struct Delayed {
+            filter: DirectForm1<f64>,
+            latency: usize,
+        }
+
+        impl Biquad<f64> for Delayed {
+            fn run(&mut self, input: f64) -> f64 {
+                self.filter.run(input)
+            }
+
+            fn update_coefficients(&mut self, new_coefficients: Coefficients<f64>) {
+                self.filter.update_coefficients(new_coefficients);
+            }
+
+            fn replace_coefficients(
+                &mut self,
+                new_coefficients: Coefficients<f64>,
+            ) -> Coefficients<f64> {
+                self.filter.replace_coefficients(new_coefficients)
+            }
+
+            fn reset_state(&mut self) {
+                self.filter.reset_state();
+            }
+
+            fn latency_samples(&self) -> usize {
+                self.latency
+            }
+        }
+
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+        let filter = DirectForm1::<f64>::new(coeffs);
+
+        let plain = Cascade::new([filter, filter]);
+        assert_eq!(plain.total_latency_samples(), 0);
+
+        let mixed = Cascade::new([
+            Delayed { filter, latency: 0 },
+            Delayed {
+                filter,
+                latency: 32,
+            },
+        ]);
+        assert_eq!(mixed.total_latency_samples(), 32);
+
+        let oversampled = Cascade::new(
+            [Delayed {
+                filter,
+                latency: 32,
+            }; 3],
+        );
+        assert_eq!(oversampled.total_latency_samples(), 96);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_chebyshev1_lowpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let response = |sections: &[Coefficients<f32>], f: f32| -> f32 {
+            let f = Hertz::<f32>::from_hz(f).unwrap();
+            sections.iter().map(|s| s.magnitude_db(f, fs)).sum()
+        };
+
+        for &order in &[2, 4, 5] {
+            for &ripple in &[0.5, 1.0, 3.0] {
+                let sections =
+                    Coefficients::<f32>::chebyshev1_lowpass(order, fs, f0, ripple).unwrap();
+                assert_eq!(sections.len(), order.div_ceil(2));
+
+                // The passband ripples between 0 dB and -ripple, which is reached again at f0
+                let passband: Vec<f32> =
+                    (1..=1000).map(|f| response(&sections, f as f32)).collect();
+                let max = passband.iter().cloned().fold(f32::MIN, f32::max);
+                let min = passband.iter().cloned().fold(f32::MAX, f32::min);
+                assert!(
+                    max.abs() < 0.01,
+                    "order {}, {} dB: max {}",
+                    order,
+                    ripple,
+                    max
+                );
+                assert!(
+                    (min + ripple).abs() < 0.01,
+                    "order {}, {} dB: min {}",
+                    order,
+                    ripple,
+                    min
+                );
+                assert!((response(&sections, f0.hz()) + ripple).abs() < 0.01);
+
+                // A steeper roll off than the Butterworth low pass with the same -3 dB point
+                if ripple == 3.0 {
+                    let butterworth =
+                        Coefficients::<f32>::butterworth_lowpass(order, fs, f0).unwrap();
+                    assert!(response(&sections, 2_000.0) < response(&butterworth, 2_000.0) - 3.0);
+                }
+            }
+        }
+
+        let butterworth = Coefficients::<f32>::butterworth_lowpass(4, fs, f0).unwrap();
+        let flat = Coefficients::<f32>::chebyshev1_lowpass(4, fs, f0, 0.0).unwrap();
+        assert_eq!(flat, butterworth);
+
+        assert!(Coefficients::<f32>::chebyshev1_lowpass(0, fs, f0, 1.0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f32>::chebyshev1_lowpass(4, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_chebyshev1_lowpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let response = |sections: &[Coefficients<f64>], f: f64| -> f64 {
+            let f = Hertz::<f64>::from_hz(f).unwrap();
+            sections.iter().map(|s| s.magnitude_db(f, fs)).sum()
+        };
+
+        for &order in &[2, 4, 5] {
+            for &ripple in &[0.5, 1.0, 3.0] {
+                let sections =
+                    Coefficients::<f64>::chebyshev1_lowpass(order, fs, f0, ripple).unwrap();
+                assert_eq!(sections.len(), order.div_ceil(2));
+
+                // The passband ripples between 0 dB and -ripple, which is reached again at f0
+                let passband: Vec<f64> =
+                    (1..=1000).map(|f| response(&sections, f as f64)).collect();
+                let max = passband.iter().cloned().fold(f64::MIN, f64::max);
+                let min = passband.iter().cloned().fold(f64::MAX, f64::min);
+                assert!(
+                    max.abs() < 0.01,
+                    "order {}, {} dB: max {}",
+                    order,
+                    ripple,
+                    max
+                );
+                assert!(
+                    (min + ripple).abs() < 0.01,
+                    "order {}, {} dB: min {}",
+                    order,
+                    ripple,
+                    min
+                );
+                assert!((response(&sections, f0.hz()) + ripple).abs() < 0.01);
+
+                // A steeper roll off than the Butterworth low pass with the same -3 dB point
+                if ripple == 3.0 {
+                    let butterworth =
+                        Coefficients::<f64>::butterworth_lowpass(order, fs, f0).unwrap();
+                    assert!(response(&sections, 2_000.0) < response(&butterworth, 2_000.0) - 3.0);
+                }
+            }
+        }
+
+        let butterworth = Coefficients::<f64>::butterworth_lowpass(4, fs, f0).unwrap();
+        let flat = Coefficients::<f64>::chebyshev1_lowpass(4, fs, f0, 0.0).unwrap();
+        assert_eq!(flat, butterworth);
+
+        assert!(Coefficients::<f64>::chebyshev1_lowpass(0, fs, f0, 1.0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f64>::chebyshev1_lowpass(4, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_chebyshev1_highpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+        let response = |sections: &[Coefficients<f32>], f: f32| -> f32 {
+            let f = Hertz::<f32>::from_hz(f).unwrap();
+            sections.iter().map(|s| s.magnitude_db(f, fs)).sum()
+        };
+
+        for &order in &[2, 4, 5] {
+            for &ripple in &[0.5, 1.0, 3.0] {
+                let sections =
+                    Coefficients::<f32>::chebyshev1_highpass(order, fs, f0, ripple).unwrap();
+                assert_eq!(sections.len(), order.div_ceil(2));
+
+                // The passband ripples between 0 dB and -ripple, which is reached again at f0
+                let passband: Vec<f32> = (0..=230)
+                    .map(|i| response(&sections, 1_000.0 + 100.0 * i as f32))
+                    .collect();
+                let max = passband.iter().cloned().fold(f32::MIN, f32::max);
+                let min = passband.iter().cloned().fold(f32::MAX, f32::min);
+                assert!(
+                    max.abs() < 0.01,
+                    "order {}, {} dB: max {}",
+                    order,
+                    ripple,
+                    max
+                );
+                assert!(
+                    (min + ripple).abs() < 0.01,
+                    "order {}, {} dB: min {}",
+                    order,
+                    ripple,
+                    min
+                );
+                assert!((response(&sections, f0.hz()) + ripple).abs() < 0.01);
+                assert!(response(&sections, 250.0) < -20.0);
+            }
+        }
+
+        // Without ripple it is the cookbook Butterworth high pass
+        let flat = Coefficients::<f32>::chebyshev1_highpass(2, fs, f0, 0.0).unwrap();
+        let butterworth =
+            Coefficients::<f32>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F32).unwrap();
+        assert!(flat[0].approx_eq(&butterworth, 1e-5));
+
+        assert!(Coefficients::<f32>::chebyshev1_highpass(0, fs, f0, 1.0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f32>::chebyshev1_highpass(4, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_chebyshev1_highpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+        let response = |sections: &[Coefficients<f64>], f: f64| -> f64 {
+            let f = Hertz::<f64>::from_hz(f).unwrap();
+            sections.iter().map(|s| s.magnitude_db(f, fs)).sum()
+        };
+
+        for &order in &[2, 4, 5] {
+            for &ripple in &[0.5, 1.0, 3.0] {
+                let sections =
+                    Coefficients::<f64>::chebyshev1_highpass(order, fs, f0, ripple).unwrap();
+                assert_eq!(sections.len(), order.div_ceil(2));
+
+                // The passband ripples between 0 dB and -ripple, which is reached again at f0
+                let passband: Vec<f64> = (0..=230)
+                    .map(|i| response(&sections, 1_000.0 + 100.0 * i as f64))
+                    .collect();
+                let max = passband.iter().cloned().fold(f64::MIN, f64::max);
+                let min = passband.iter().cloned().fold(f64::MAX, f64::min);
+                assert!(
+                    max.abs() < 0.01,
+                    "order {}, {} dB: max {}",
+                    order,
+                    ripple,
+                    max
+                );
+                assert!(
+                    (min + ripple).abs() < 0.01,
+                    "order {}, {} dB: min {}",
+                    order,
+                    ripple,
+                    min
+                );
+                assert!((response(&sections, f0.hz()) + ripple).abs() < 0.01);
+                assert!(response(&sections, 250.0) < -20.0);
+            }
+        }
+
+        // Without ripple it is the cookbook Butterworth high pass
+        let flat = Coefficients::<f64>::chebyshev1_highpass(2, fs, f0, 0.0).unwrap();
+        let butterworth =
+            Coefficients::<f64>::from_params(Type::HighPass, fs, f0, Q_BUTTERWORTH_F64).unwrap();
+        assert!(flat[0].approx_eq(&butterworth, 1e-12));
+
+        assert!(Coefficients::<f64>::chebyshev1_highpass(0, fs, f0, 1.0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Coefficients::<f64>::chebyshev1_highpass(4, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_generate_chirp_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let (f_start, f_end) = (100.0f32, 10_000.0f32);
+        let chirp = generate_chirp(fs, f_start.hz(), f_end.hz(), 480_000).unwrap();
+        assert_eq!(chirp.len(), 480_000);
+        assert_eq!(chirp[0], 0.0);
+        assert!(chirp.iter().all(|x| x.abs() <= 1.0));
+
+        // The instantaneous frequency from the interpolated rising zero crossings
+        let crossings: Vec<f32> = chirp
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] < 0.0 && w[1] >= 0.0)
+            .map(|(n, w)| n as f32 + w[0] / (w[0] - w[1]))
+            .collect();
+        let frequency = |periods: &[f32]| {
+            fs.hz() * (periods.len() - 1) as f32 / (periods[periods.len() - 1] - periods[0])
+        };
+
+        let start = frequency(&crossings[..3]);
+        assert!((start - f_start).abs() < 0.01 * f_start, "{} Hz", start);
+        let end = frequency(&crossings[crossings.len() - 11..]);
+        assert!((end - f_end).abs() < 0.01 * f_end, "{} Hz", end);
+
+        // Downward sweeps and constant tones
+        let down = generate_chirp(fs, f_end.hz(), f_start.hz(), 4_800).unwrap();
+        assert!((down[1] - libm::sinf(2.0 * core::f32::consts::PI * f_end / fs.hz())).abs() < 0.01);
+        let tone = generate_chirp(fs, 1.khz(), 1.khz(), 48).unwrap();
+        assert!(tone.iter().all(|x| x.is_finite()));
+        assert_eq!(generate_chirp(fs, 1.khz(), 1.khz(), 1).unwrap(), [0.0]);
+
+        assert_eq!(
+            generate_chirp(fs, f_start.hz(), 30.khz(), 100),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_generate_chirp_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let (f_start, f_end) = (100.0f64, 10_000.0f64);
+        let chirp = generate_chirp(fs, f_start.hz(), f_end.hz(), 480_000).unwrap();
+        assert_eq!(chirp.len(), 480_000);
+        assert_eq!(chirp[0], 0.0);
+        assert!(chirp.iter().all(|x| x.abs() <= 1.0));
+
+        // The instantaneous frequency from the interpolated rising zero crossings
+        let crossings: Vec<f64> = chirp
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] < 0.0 && w[1] >= 0.0)
+            .map(|(n, w)| n as f64 + w[0] / (w[0] - w[1]))
+            .collect();
+        let frequency = |periods: &[f64]| {
+            fs.hz() * (periods.len() - 1) as f64 / (periods[periods.len() - 1] - periods[0])
+        };
+
+        let start = frequency(&crossings[..3]);
+        assert!((start - f_start).abs() < 0.01 * f_start, "{} Hz", start);
+        let end = frequency(&crossings[crossings.len() - 11..]);
+        assert!((end - f_end).abs() < 0.01 * f_end, "{} Hz", end);
+
+        // Downward sweeps and constant tones
+        let down = generate_chirp(fs, f_end.hz(), f_start.hz(), 4_800).unwrap();
+        assert!((down[1] - libm::sin(2.0 * core::f64::consts::PI * f_end / fs.hz())).abs() < 0.01);
+        let tone = generate_chirp(fs, 1.khz(), 1.khz(), 48).unwrap();
+        assert!(tone.iter().all(|x| x.is_finite()));
+        assert_eq!(generate_chirp(fs, 1.khz(), 1.khz(), 1).unwrap(), [0.0]);
+
+        assert_eq!(
+            generate_chirp(fs, f_start.hz(), 30.khz(), 100),
+            Err(Errors::OutsideNyquist)
+        );
+    }
+
+    #[test]
+    fn test_retunable_retune_sample_rate_f32() {
+        let f0: Hertz<f32> = 1.khz();
+        let filter = Type::PeakingEQ(6.0);
+        let mut band = RetunableFilter::<f32>::new(filter, 44.1.khz(), f0, 2.0).unwrap();
+        for i in 0..64 {
+            band.run(if i % 8 == 0 { 1.0 } else { 0.0 });
+        }
+        let mut reference = DirectForm1::<f32>::new(band.coefficients());
+        for i in 0..64 {
+            reference.run(if i % 8 == 0 { 1.0 } else { 0.0 });
+        }
+
+        // The coefficients match a fresh design at the new rate, keeping the state
+        band.retune_sample_rate(48.khz(), false).unwrap();
+        let fresh = Coefficients::<f32>::from_params(filter, 48.khz(), f0, 2.0).unwrap();
+        assert_eq!(band.coefficients(), fresh);
+        assert_eq!(band.sample_rate(), 48.khz());
+        reference.update_coefficients(fresh);
+        for _ in 0..16 {
+            assert_eq!(band.run(0.0), reference.run(0.0));
+        }
+
+        // Or starting from silence
+        band.retune_sample_rate(96.khz(), true).unwrap();
+        assert_eq!(band.run(0.0), 0.0);
+
+        assert_eq!(
+            band.retune_sample_rate(1.khz(), false),
+            Err(Errors::OutsideNyquist)
+        );
+        assert_eq!(band.sample_rate(), 96.khz());
+    }
+
+    #[test]
+    fn test_retunable_retune_sample_rate_f64() {
+        let f0: Hertz<f64> = 1.khz();
+        let filter = Type::PeakingEQ(6.0);
+        let mut band = RetunableFilter::<f64>::new(filter, 44.1.khz(), f0, 2.0).unwrap();
+        for i in 0..64 {
+            band.run(if i % 8 == 0 { 1.0 } else { 0.0 });
+        }
+        let mut reference = DirectForm1::<f64>::new(band.coefficients());
+        for i in 0..64 {
+            reference.run(if i % 8 == 0 { 1.0 } else { 0.0 });
+        }
+
+        // The coefficients match a fresh design at the new rate, keeping the state
+        band.retune_sample_rate(48.khz(), false).unwrap();
+        let fresh = Coefficients::<f64>::from_params(filter, 48.khz(), f0, 2.0).unwrap();
+        assert_eq!(band.coefficients(), fresh);
+        assert_eq!(band.sample_rate(), 48.khz());
+        reference.update_coefficients(fresh);
+        for _ in 0..16 {
+            assert_eq!(band.run(0.0), reference.run(0.0));
+        }
+
+        // Or starting from silence
+        band.retune_sample_rate(96.khz(), true).unwrap();
+        assert_eq!(band.run(0.0), 0.0);
+
+        assert_eq!(
+            band.retune_sample_rate(1.khz(), false),
+            Err(Errors::OutsideNyquist)
+        );
+        assert_eq!(band.sample_rate(), 96.khz());
+    }
+
+    #[test]
+    fn test_from_params_normalized_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 2.khz();
+        let dc: Hertz<f32> = 1.hz();
+        let nyquist: Hertz<f32> = 24.khz();
+
+        let filters = [
+            (Type::BandPass, f0),
+            (Type::PeakingEQ(9.0), f0),
+            (Type::PeakingEQ(-6.0), f0),
+            (Type::ConstantQPeaking(6.0), f0),
+            (Type::LowShelf(6.0), dc),
+            (Type::HighShelf(-12.0), nyquist),
+            (Type::LowPass, dc),
+            (Type::HighPass, nyquist),
+            (Type::Notch, dc),
+            (Type::AllPass, dc),
+        ];
+
+        for &(filter, reference) in filters.iter() {
+            for &q in [0.3, Q_BUTTERWORTH_F32, 2.0, 10.0].iter() {
+                let coeffs =
+                    Coefficients::<f32>::from_params_normalized(filter, fs, f0, q).unwrap();
+                assert!(coeffs.magnitude_db(reference, fs).abs() < 1e-3);
+
+                // Only the numerator is scaled
+                let plain = Coefficients::<f32>::from_params(filter, fs, f0, q).unwrap();
+                assert_eq!((coeffs.a1, coeffs.a2), (plain.a1, plain.a2));
+            }
+        }
+
+        assert_eq!(
+            Coefficients::<f32>::from_params_normalized(Type::BandPass, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+
+        // A high pass at Nyquist has no gain left at Nyquist to normalize
+        assert_eq!(
+            Coefficients::<f32>::from_params_normalized(Type::HighPass, fs, 24.khz(), 1.0),
+            Err(Errors::GainUnreachable)
+        );
+    }
+
+    #[test]
+    fn test_from_params_normalized_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 2.khz();
+        let dc: Hertz<f64> = 1.hz();
+        let nyquist: Hertz<f64> = 24.khz();
+
+        let filters = [
+            (Type::BandPass, f0),
+            (Type::PeakingEQ(9.0), f0),
+            (Type::PeakingEQ(-6.0), f0),
+            (Type::ConstantQPeaking(6.0), f0),
+            (Type::LowShelf(6.0), dc),
+            (Type::HighShelf(-12.0), nyquist),
+            (Type::LowPass, dc),
+            (Type::HighPass, nyquist),
+            (Type::Notch, dc),
+            (Type::AllPass, dc),
+        ];
+
+        for &(filter, reference) in filters.iter() {
+            for &q in [0.3, Q_BUTTERWORTH_F64, 2.0, 10.0].iter() {
+                let coeffs =
+                    Coefficients::<f64>::from_params_normalized(filter, fs, f0, q).unwrap();
+                assert!(coeffs.magnitude_db(reference, fs).abs() < 1e-3);
+
+                // Only the numerator is scaled
+                let plain = Coefficients::<f64>::from_params(filter, fs, f0, q).unwrap();
+                assert_eq!((coeffs.a1, coeffs.a2), (plain.a1, plain.a2));
+            }
+        }
+
+        assert_eq!(
+            Coefficients::<f64>::from_params_normalized(Type::BandPass, fs, 30.khz(), 1.0),
+            Err(Errors::OutsideNyquist)
+        );
+
+        // A high pass at Nyquist has no gain left at Nyquist to normalize
+        assert_eq!(
+            Coefficients::<f64>::from_params_normalized(Type::HighPass, fs, 24.khz(), 1.0),
+            Err(Errors::GainUnreachable)
+        );
+    }
+
+    #[test]
+    fn test_try_run_f32() {
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), 100.0).unwrap();
+        let mut biquad = DirectForm1::<f32>::new(coeffs);
+
+        assert_eq!(biquad.try_run(1.0), Ok(coeffs.b0));
+
+        // The resonance overshoots an input at full range, which overflows
+        let mut result = Ok(0.0);
+        for _ in 0..1000 {
+            result = biquad.try_run(f32::MAX);
+            match result {
+                Ok(output) => assert!(output.is_finite()),
+                Err(_) => break,
+            }
+        }
+        assert_eq!(result, Err(Errors::NonFiniteOutput));
+
+        biquad.reset_state();
+        assert_eq!(biquad.try_run(0.0), Ok(0.0));
+    }
+
+    #[test]
+    fn test_try_run_f64() {
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, 48.khz(), 1.khz(), 100.0).unwrap();
+        let mut biquad = DirectForm1::<f64>::new(coeffs);
+
+        assert_eq!(biquad.try_run(1.0), Ok(coeffs.b0));
+
+        // The resonance overshoots an input at full range, which overflows
+        let mut result = Ok(0.0);
+        for _ in 0..1000 {
+            result = biquad.try_run(f64::MAX);
+            match result {
+                Ok(output) => assert!(output.is_finite()),
+                Err(_) => break,
+            }
+        }
+        assert_eq!(result, Err(Errors::NonFiniteOutput));
+
+        biquad.reset_state();
+        assert_eq!(biquad.try_run(0.0), Ok(0.0));
+    }
+
+    #[test]
+    fn test_multichannel_f32() {
+        let fs = 1.khz();
+        let coeffs =
+            Coefficients::<f32>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F32)
+                .unwrap();
+
+        let mut stereo = MultiChannel::<DirectForm1<f32>, 2>::new(coeffs);
+        let mut left_ref = DirectForm1::<f32>::new(coeffs);
+        let mut right_ref = DirectForm1::<f32>::new(coeffs);
+
+        // The right channel stays silent until its own impulse, too
+        for i in 0..100 {
+            let left = if i == 0 { 1.0 } else { 0.0 };
+            let right = if i == 50 { -1.0 } else { 0.0 };
+            let [a, b] = stereo.run([left, right]);
+            assert_eq!(a, left_ref.run(left));
+            assert_eq!(b, right_ref.run(right));
+            if i < 50 {
+                assert_eq!(b, 0.0);
+            }
+        }
+
+        let high = Coefficients::<f32>::from_params(Type::HighPass, fs, 10.hz(), Q_BUTTERWORTH_F32)
+            .unwrap();
+        stereo.update_coefficients(high);
+        assert_eq!(stereo.coefficients(), high);
+        assert!(stereo
+            .channels()
+            .iter()
+            .all(|channel| channel.coefficients() == high));
+
+        // A single channel is the smallest multichannel filter
+        let mut mono = MultiChannel::<DirectForm2Transposed<f32>, 1>::new(coeffs);
+        let mut mono_ref = DirectForm2Transposed::<f32>::new(coeffs);
+        assert_eq!(mono.coefficients(), coeffs);
+        assert_eq!(mono.run([1.0]), [mono_ref.run(1.0)]);
+    }
+
+    #[test]
+    fn test_multichannel_f64() {
+        let fs = 1.khz();
+        let coeffs =
+            Coefficients::<f64>::from_params(Type::LowPass, fs, 10.hz(), Q_BUTTERWORTH_F64)
+                .unwrap();
+
+        let mut stereo = MultiChannel::<DirectForm1<f64>, 2>::new(coeffs);
+        let mut left_ref = DirectForm1::<f64>::new(coeffs);
+        let mut right_ref = DirectForm1::<f64>::new(coeffs);
+
+        // The right channel stays silent until its own impulse, too
+        for i in 0..100 {
+            let left = if i == 0 { 1.0 } else { 0.0 };
+            let right = if i == 50 { -1.0 } else { 0.0 };
+            let [a, b] = stereo.run([left, right]);
+            assert_eq!(a, left_ref.run(left));
+            assert_eq!(b, right_ref.run(right));
+            if i < 50 {
+                assert_eq!(b, 0.0);
+            }
+        }
+
+        let high = Coefficients::<f64>::from_params(Type::HighPass, fs, 10.hz(), Q_BUTTERWORTH_F64)
+            .unwrap();
+        stereo.update_coefficients(high);
+        assert_eq!(stereo.coefficients(), high);
+        assert!(stereo
+            .channels()
+            .iter()
+            .all(|channel| channel.coefficients() == high));
+
+        // A single channel is the smallest multichannel filter
+        let mut mono = MultiChannel::<DirectForm2Transposed<f64>, 1>::new(coeffs);
+        let mut mono_ref = DirectForm2Transposed::<f64>::new(coeffs);
+        assert_eq!(mono.coefficients(), coeffs);
+        assert_eq!(mono.run([1.0]), [mono_ref.run(1.0)]);
+    }
+
+    #[test]
+    fn test_retunable_set_frequency_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let filter = Type::LowPass;
+        let q = 2.0;
+        let mut sweep = RetunableFilter::<f32>::new(filter, fs, 100.hz(), q).unwrap();
+        let mut reference = DirectForm1::<f32>::new(sweep.coefficients());
+
+        for step in 1..=20 {
+            let f0 = Hertz::<f32>::from_hz(100.0 * step as f32).unwrap();
+            sweep.set_frequency(f0).unwrap();
+
+            let rebuilt = Coefficients::<f32>::from_params(filter, fs, f0, q).unwrap();
+            assert_eq!(sweep.coefficients(), rebuilt);
+            assert_eq!(sweep.frequency(), f0);
+            assert_eq!(sweep.q(), q);
+
+            // The state carries over the retuning
+            reference.update_coefficients(rebuilt);
+            for _ in 0..8 {
+                assert_eq!(sweep.run(1.0), reference.run(1.0));
+            }
+        }
+
+        let before = sweep.coefficients();
+        assert_eq!(sweep.set_frequency(30.khz()), Err(Errors::OutsideNyquist));
+        assert_eq!(sweep.coefficients(), before);
+        assert_eq!(sweep.frequency(), 2.khz());
+    }
+
+    #[test]
+    fn test_retunable_set_frequency_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let filter = Type::LowPass;
+        let q = 2.0;
+        let mut sweep = RetunableFilter::<f64>::new(filter, fs, 100.hz(), q).unwrap();
+        let mut reference = DirectForm1::<f64>::new(sweep.coefficients());
+
+        for step in 1..=20 {
+            let f0 = Hertz::<f64>::from_hz(100.0 * step as f64).unwrap();
+            sweep.set_frequency(f0).unwrap();
+
+            let rebuilt = Coefficients::<f64>::from_params(filter, fs, f0, q).unwrap();
+            assert_eq!(sweep.coefficients(), rebuilt);
+            assert_eq!(sweep.frequency(), f0);
+            assert_eq!(sweep.q(), q);
+
+            // The state carries over the retuning
+            reference.update_coefficients(rebuilt);
+            for _ in 0..8 {
+                assert_eq!(sweep.run(1.0), reference.run(1.0));
+            }
+        }
+
+        let before = sweep.coefficients();
+        assert_eq!(sweep.set_frequency(30.khz()), Err(Errors::OutsideNyquist));
+        assert_eq!(sweep.coefficients(), before);
+        assert_eq!(sweep.frequency(), 2.khz());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_bessel_lowpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let mut bessel = Cascade::<DirectForm1<f32>, 2>::bessel_lowpass(4, fs, f0).unwrap();
+        let mut reference = Coefficients::<f32>::bessel_lowpass_sections(4, fs, f0)
+            .unwrap()
+            .into_iter()
+            .map(DirectForm1::<f32>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(bessel.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm1<f32>, 2>::bessel_lowpass(4, fs, 30.khz()).err(),
+            Some(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_bessel_lowpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let mut bessel = Cascade::<DirectForm1<f64>, 2>::bessel_lowpass(4, fs, f0).unwrap();
+        let mut reference = Coefficients::<f64>::bessel_lowpass_sections(4, fs, f0)
+            .unwrap()
+            .into_iter()
+            .map(DirectForm1::<f64>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(bessel.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm1<f64>, 2>::bessel_lowpass(4, fs, 30.khz()).err(),
+            Some(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_bessel_lowpass_stage_count() {
+        assert_eq!(
+            Cascade::<DirectForm1<f32>, 2>::bessel_lowpass(5, 48.khz(), 1.khz()).err(),
+            Some(Errors::StageCountMismatch)
+        );
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f64>, 3>::bessel_lowpass(4, 48.khz(), 1.khz()).err(),
+            Some(Errors::StageCountMismatch)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_chebyshev1_lowpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let mut chebyshev =
+            Cascade::<DirectForm1<f32>, 3>::chebyshev1_lowpass(5, fs, f0, 1.0).unwrap();
+        let sections = Coefficients::<f32>::chebyshev1_lowpass(5, fs, f0, 1.0).unwrap();
+        assert!(chebyshev
+            .stages()
+            .iter()
+            .map(|s| s.coefficients())
+            .eq(sections.iter().cloned()));
+
+        let mut reference = sections
+            .into_iter()
+            .map(DirectForm1::<f32>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(chebyshev.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm1<f32>, 3>::chebyshev1_lowpass(5, fs, 30.khz(), 1.0).err(),
+            Some(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_chebyshev1_lowpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let mut chebyshev =
+            Cascade::<DirectForm1<f64>, 3>::chebyshev1_lowpass(5, fs, f0, 1.0).unwrap();
+        let sections = Coefficients::<f64>::chebyshev1_lowpass(5, fs, f0, 1.0).unwrap();
+        assert!(chebyshev
+            .stages()
+            .iter()
+            .map(|s| s.coefficients())
+            .eq(sections.iter().cloned()));
+
+        let mut reference = sections
+            .into_iter()
+            .map(DirectForm1::<f64>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(chebyshev.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm1<f64>, 3>::chebyshev1_lowpass(5, fs, 30.khz(), 1.0).err(),
+            Some(Errors::OutsideNyquist)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_chebyshev1_highpass_f32() {
+        let fs: Hertz<f32> = 48.khz();
+        let f0: Hertz<f32> = 1.khz();
+
+        let mut chebyshev =
+            Cascade::<DirectForm2Transposed<f32>, 3>::chebyshev1_highpass(5, fs, f0, 1.0).unwrap();
+        let sections = Coefficients::<f32>::chebyshev1_highpass(5, fs, f0, 1.0).unwrap();
+        assert!(chebyshev
+            .stages()
+            .iter()
+            .map(|s| s.coefficients())
+            .eq(sections.iter().cloned()));
+
+        let mut reference = sections
+            .into_iter()
+            .map(DirectForm2Transposed::<f32>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(chebyshev.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f32>, 2>::chebyshev1_highpass(5, fs, f0, 1.0).err(),
+            Some(Errors::StageCountMismatch)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_chebyshev1_highpass_f64() {
+        let fs: Hertz<f64> = 48.khz();
+        let f0: Hertz<f64> = 1.khz();
+
+        let mut chebyshev =
+            Cascade::<DirectForm2Transposed<f64>, 3>::chebyshev1_highpass(5, fs, f0, 1.0).unwrap();
+        let sections = Coefficients::<f64>::chebyshev1_highpass(5, fs, f0, 1.0).unwrap();
+        assert!(chebyshev
+            .stages()
+            .iter()
+            .map(|s| s.coefficients())
+            .eq(sections.iter().cloned()));
+
+        let mut reference = sections
+            .into_iter()
+            .map(DirectForm2Transposed::<f64>::new)
+            .collect::<Vec<_>>();
+        for _ in 0..64 {
+            let expected = reference.iter_mut().fold(1.0, |x, stage| stage.run(x));
+            assert_eq!(chebyshev.run(1.0), expected);
+        }
+
+        assert_eq!(
+            Cascade::<DirectForm2Transposed<f64>, 2>::chebyshev1_highpass(5, fs, f0, 1.0).err(),
+            Some(Errors::StageCountMismatch)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cascade_chebyshev1_lowpass_stage_count() {
+        assert_eq!(
+            Cascade::<DirectForm1<f32>, 2>::chebyshev1_lowpass(5, 48.khz(), 1.khz(), 1.0).err(),
+            Some(Errors::StageCountMismatch)
+        );
+    }
 }
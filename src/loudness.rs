@@ -0,0 +1,85 @@
+//! # loudness
+//!
+//! Loudness compensation, boosting the bass and treble at low playback levels to compensate for
+//! the reduced sensitivity of hearing at the extremes of the audible range. The compensation is
+//! based on the equal-loudness contours of
+//! [ISO 226:2003](https://en.wikipedia.org/wiki/Equal-loudness_contour), comparing the contours at
+//! the playback level to the contour at a reference level of 83 dB SPL.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! // Compensation for listening at 50 dB SPL
+//! let mut loudness = Cascade::<DirectForm1<f32>, 2>::loudness_compensation(48.khz(), 50.0).unwrap();
+//!
+//! let output = loudness.run(1.0);
+//! ```
+
+use crate::float::Float;
+use crate::{Cascade, Coefficients, Decibels, DirectForm1, Errors, Hertz, Type};
+use libm::{log10, pow};
+
+/// Playback level, in dB SPL, at which no compensation is applied
+pub const LOUDNESS_REFERENCE_DB_SPL: f64 = 83.0;
+
+// ISO 226:2003 parameters at 50 Hz and 10 kHz, as (alpha_f, L_U, T_f)
+const ISO226_50_HZ: (f64, f64, f64) = (0.432, -15.9, 44.0);
+const ISO226_10_KHZ: (f64, f64, f64) = (0.271, -10.7, 13.9);
+
+// Corner frequencies of the shelves, placed so the full gain is reached at 50 Hz and 10 kHz
+const BASS_SHELF_HZ: f64 = 200.0;
+const TREBLE_SHELF_HZ: f64 = 5_000.0;
+
+/// Sound pressure level of the equal-loudness contour at `phon` for the given ISO 226 parameters
+fn iso226_spl((af, lu, tf): (f64, f64, f64), phon: f64) -> f64 {
+    let a = 4.47e-3 * (pow(10.0, 0.025 * phon) - 1.15)
+        + pow(0.4 * pow(10.0, (tf + lu) / 10.0 - 9.0), af);
+
+    10.0 / af * log10(a) - lu + 94.0
+}
+
+/// The bass and treble gains, in decibels, compensating playback at `playback_db_spl`. The
+/// level is limited to the 20-90 phon range where the contours are defined.
+fn compensation_gains(playback_db_spl: f64) -> (f64, f64) {
+    let level = playback_db_spl.clamp(20.0, 90.0);
+    let reference = LOUDNESS_REFERENCE_DB_SPL;
+
+    // The extra level needed at the band edges, relative to the level change at 1 kHz
+    let gain =
+        |params| iso226_spl(params, level) - iso226_spl(params, reference) - (level - reference);
+
+    (gain(ISO226_50_HZ), gain(ISO226_10_KHZ))
+}
+
+impl<T: Float> Cascade<DirectForm1<T>, 2> {
+    /// Creates a low shelf and high shelf cascade compensating the equal-loudness contours when
+    /// listening at `playback_db_spl`, relative to `LOUDNESS_REFERENCE_DB_SPL`. Lower playback
+    /// levels give larger boosts, and the sampling frequency must allow for the treble shelf.
+    pub fn loudness_compensation(
+        fs: Hertz<T>,
+        playback_db_spl: impl Into<Decibels<T>>,
+    ) -> Result<Self, Errors> {
+        let (bass, treble) = compensation_gains(playback_db_spl.into().db().to_f64());
+        let q = T::from_f64(crate::Q_BUTTERWORTH_F64);
+
+        let low = Coefficients::<T>::from_params(
+            Type::LowShelf(T::from_f64(bass)),
+            fs,
+            Hertz::<T>::from_hz(T::from_f64(BASS_SHELF_HZ))?,
+            q,
+        )?;
+        let high = Coefficients::<T>::from_params(
+            Type::HighShelf(T::from_f64(treble)),
+            fs,
+            Hertz::<T>::from_hz(T::from_f64(TREBLE_SHELF_HZ))?,
+            q,
+        )?;
+
+        Ok(Cascade::new([
+            DirectForm1::<T>::new(low),
+            DirectForm1::<T>::new(high),
+        ]))
+    }
+}
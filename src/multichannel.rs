@@ -0,0 +1,160 @@
+//! # multichannel
+//!
+//! Containers for filtering frames of several channels at once.
+//!
+//! `PerChannelBiquad` holds an independent biquad, with its own coefficients, for every channel,
+//! e.g. for the legs of a crossover which each need a different filter. `MultiChannel` applies the
+//! same coefficients to all channels while keeping a separate state per channel, e.g. for the same
+//! EQ on both sides of a stereo signal.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let fs = 48.khz();
+//! let low = Coefficients::<f32>::from_params(Type::LowPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+//! let high = Coefficients::<f32>::from_params(Type::HighPass, fs, 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! let mut crossover = PerChannelBiquad::new([
+//!     DirectForm2Transposed::<f32>::new(low),
+//!     DirectForm2Transposed::<f32>::new(high),
+//! ]);
+//!
+//! let [low_out, high_out] = crossover.run([1.0, 1.0]);
+//!
+//! let mut stereo = MultiChannel::<DirectForm1<f32>, 2>::new(low);
+//! let [left, right] = stereo.run([1.0, -1.0]);
+//! ```
+
+use crate::{Biquad, Coefficients, Section};
+
+/// A biquad per channel, where every channel has its own coefficients and state
+#[derive(Copy, Clone, Debug)]
+pub struct PerChannelBiquad<B, const CH: usize> {
+    channels: [B; CH],
+}
+
+impl<B, const CH: usize> PerChannelBiquad<B, CH> {
+    /// Creates a multichannel filter from one biquad per channel
+    pub fn new(channels: [B; CH]) -> Self {
+        PerChannelBiquad { channels }
+    }
+
+    /// Filters a frame, applying the biquad of each channel on its own sample
+    pub fn run<T: Copy>(&mut self, frame: [T; CH]) -> [T; CH]
+    where
+        B: Biquad<T>,
+    {
+        let mut out = frame;
+        for (sample, channel) in out.iter_mut().zip(self.channels.iter_mut()) {
+            *sample = channel.run(*sample);
+        }
+
+        out
+    }
+
+    /// Updating of the coefficients of a single channel
+    pub fn update_coefficients<T>(&mut self, channel: usize, new_coefficients: Coefficients<T>)
+    where
+        B: Biquad<T>,
+    {
+        self.channels[channel].update_coefficients(new_coefficients);
+    }
+
+    /// Set the internal state of all channels to 0 without allocation
+    pub fn reset_state<T>(&mut self)
+    where
+        B: Biquad<T>,
+    {
+        for channel in self.channels.iter_mut() {
+            channel.reset_state();
+        }
+    }
+
+    /// The biquads of the channels
+    pub fn channels_mut(&mut self) -> &mut [B; CH] {
+        &mut self.channels
+    }
+}
+
+/// A biquad per channel with the same coefficients, where every channel keeps its own state. The
+/// channels are complete biquads, so each holds its own copy of the coefficients, which are kept
+/// equal by updating all channels at once.
+#[derive(Copy, Clone, Debug)]
+pub struct MultiChannel<F, const CH: usize> {
+    channels: [F; CH],
+}
+
+impl<F, const CH: usize> MultiChannel<F, CH> {
+    // Evaluated at compile time for every `CH` passed to `new`
+    const NOT_EMPTY: () = assert!(CH > 0, "a MultiChannel needs at least one channel");
+}
+
+impl<F: Section, const CH: usize> MultiChannel<F, CH> {
+    /// Creates a multichannel filter with a copy of `coefficients` for every channel, with zeroed
+    /// states. `CH` must be at least 1, no channels are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use biquad::*;
+    ///
+    /// let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), 0.7);
+    /// let empty = MultiChannel::<DirectForm1<f32>, 0>::new(coeffs.unwrap());
+    /// ```
+    pub fn new(coefficients: Coefficients<F::Sample>) -> Self
+    where
+        F::Sample: Copy,
+    {
+        let () = Self::NOT_EMPTY;
+
+        MultiChannel {
+            channels: core::array::from_fn(|_| F::from_coefficients(coefficients)),
+        }
+    }
+
+    /// The coefficients of all channels, read from the first one
+    pub fn coefficients(&self) -> Coefficients<F::Sample> {
+        self.channels[0].coefficients()
+    }
+}
+
+impl<F, const CH: usize> MultiChannel<F, CH> {
+    /// Filters a frame, e.g. one sample per channel of interleaved audio, running every sample
+    /// through the state of its own channel
+    pub fn run<T: Copy>(&mut self, frame: [T; CH]) -> [T; CH]
+    where
+        F: Biquad<T>,
+    {
+        let mut out = frame;
+        for (sample, channel) in out.iter_mut().zip(self.channels.iter_mut()) {
+            *sample = channel.run(*sample);
+        }
+
+        out
+    }
+
+    /// Updating of the coefficients of all channels at once, keeping the states
+    pub fn update_coefficients<T: Copy>(&mut self, new_coefficients: Coefficients<T>)
+    where
+        F: Biquad<T>,
+    {
+        for channel in self.channels.iter_mut() {
+            channel.update_coefficients(new_coefficients);
+        }
+    }
+
+    /// Set the internal state of all channels to 0 without allocation
+    pub fn reset_state<T>(&mut self)
+    where
+        F: Biquad<T>,
+    {
+        for channel in self.channels.iter_mut() {
+            channel.reset_state();
+        }
+    }
+
+    /// The biquads of the channels
+    pub fn channels(&self) -> &[F; CH] {
+        &self.channels
+    }
+}
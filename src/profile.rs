@@ -0,0 +1,137 @@
+//! # profile
+//!
+//! Emulation of the coefficient quantization and accumulator behavior of a specific DSP chip, for
+//! bit-exact recreations of hardware EQs. A `HardwareProfile` describes how the chip stores
+//! coefficients and how its accumulator treats intermediate sums.
+//! `Coefficients::from_params_profiled` designs coefficients as the chip would store them, and
+//! `ProfiledDirectForm1` runs them with the accumulator behavior of the chip.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! // A chip storing coefficients in Q2.14, truncating towards zero
+//! struct Q14;
+//!
+//! impl HardwareProfile<f32> for Q14 {
+//!     fn round_coefficient(&self, c: f32) -> f32 {
+//!         (c * 16384.0) as i16 as f32 / 16384.0
+//!     }
+//! }
+//!
+//! let coeffs = Coefficients::<f32>::from_params_profiled(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32, &Q14).unwrap();
+//! let mut biquad = ProfiledDirectForm1::new(coeffs, Q14);
+//!
+//! let output = biquad.run(1.0);
+//! ```
+
+use crate::{float::Float, frequency::Hertz, Biquad, Coefficients, Errors, Type};
+
+/// The arithmetic of a DSP chip, as the rounding applied to stored coefficients and to the
+/// accumulator after every multiply-accumulate step
+pub trait HardwareProfile<T> {
+    /// Quantizes a coefficient to the value the chip stores
+    fn round_coefficient(&self, c: T) -> T;
+
+    /// Applies the accumulator behavior, such as truncation or saturation, to an intermediate
+    /// sum. The accumulator is exact by default.
+    fn accumulate(&self, acc: T) -> T {
+        acc
+    }
+}
+
+impl<T: Float> Coefficients<T> {
+    /// Creates coefficients the same way as `from_params` and quantizes them with
+    /// `HardwareProfile::round_coefficient` of `profile`
+    pub fn from_params_profiled<P: HardwareProfile<T>>(
+        filter: Type<T>,
+        fs: Hertz<T>,
+        f0: Hertz<T>,
+        q_value: T,
+        profile: &P,
+    ) -> Result<Coefficients<T>, Errors> {
+        let coeffs = Coefficients::<T>::from_params(filter, fs, f0, q_value)?;
+
+        Ok(round_coefficients(&coeffs, profile))
+    }
+}
+
+fn round_coefficients<T: Float, P: HardwareProfile<T>>(
+    coeffs: &Coefficients<T>,
+    profile: &P,
+) -> Coefficients<T> {
+    Coefficients {
+        a1: profile.round_coefficient(coeffs.a1),
+        a2: profile.round_coefficient(coeffs.a2),
+        b0: profile.round_coefficient(coeffs.b0),
+        b1: profile.round_coefficient(coeffs.b1),
+        b2: profile.round_coefficient(coeffs.b2),
+    }
+}
+
+/// A Direct Form 1 biquad with the arithmetic of a `HardwareProfile`. The coefficients are
+/// quantized when set and the accumulator behavior is applied after every multiply-accumulate
+/// step, in the order `b0`, `b1`, `b2`, `a1`, `a2`.
+#[derive(Copy, Clone, Debug)]
+pub struct ProfiledDirectForm1<P, T> {
+    y1: T,
+    y2: T,
+    x1: T,
+    x2: T,
+    coeffs: Coefficients<T>,
+    profile: P,
+}
+
+impl<P: HardwareProfile<T>, T: Float> ProfiledDirectForm1<P, T> {
+    /// Creates a biquad from a set of filter coefficients, quantized by `profile`
+    pub fn new(coefficients: Coefficients<T>, profile: P) -> Self {
+        ProfiledDirectForm1 {
+            y1: T::ZERO,
+            y2: T::ZERO,
+            x1: T::ZERO,
+            x2: T::ZERO,
+            coeffs: round_coefficients(&coefficients, &profile),
+            profile,
+        }
+    }
+
+    /// The quantized coefficients in use
+    pub fn coefficients(&self) -> Coefficients<T> {
+        self.coeffs
+    }
+}
+
+impl<P: HardwareProfile<T>, T: Float> Biquad<T> for ProfiledDirectForm1<P, T> {
+    fn run(&mut self, input: T) -> T {
+        let p = &self.profile;
+        let mut acc = p.accumulate(self.coeffs.b0 * input);
+        acc = p.accumulate(acc + self.coeffs.b1 * self.x1);
+        acc = p.accumulate(acc + self.coeffs.b2 * self.x2);
+        acc = p.accumulate(acc - self.coeffs.a1 * self.y1);
+        let out = p.accumulate(acc - self.coeffs.a2 * self.y2);
+
+        self.x2 = self.x1;
+        self.x1 = input;
+        self.y2 = self.y1;
+        self.y1 = out;
+
+        out
+    }
+
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
+        self.coeffs = round_coefficients(&new_coefficients, &self.profile);
+    }
+
+    fn replace_coefficients(&mut self, new_coefficients: Coefficients<T>) -> Coefficients<T> {
+        let new_coefficients = round_coefficients(&new_coefficients, &self.profile);
+        core::mem::replace(&mut self.coeffs, new_coefficients)
+    }
+
+    fn reset_state(&mut self) {
+        self.x1 = T::ZERO;
+        self.x2 = T::ZERO;
+        self.y1 = T::ZERO;
+        self.y2 = T::ZERO;
+    }
+}
@@ -0,0 +1,127 @@
+//! # retunable
+//!
+//! A biquad which keeps the parameters it was designed from, so that a single parameter can be
+//! changed while the others are kept, e.g. when the Q handle of an EQ band is dragged in a UI. The
+//! filter runs in Direct Form 1, which introduces minimal artifacts under retuning, and keeps its
+//! state when retuned, including when the sample rate changes.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let mut band = RetunableFilter::<f32>::new(Type::PeakingEQ(6.0), 48.khz(), 1.khz(), 1.0).unwrap();
+//!
+//! let output = band.run(1.0);
+//!
+//! // Narrow the band, keeping the center frequency and gain
+//! band.set_q(4.0).unwrap();
+//!
+//! // Move the band, keeping the Q value and gain
+//! band.set_frequency(2.khz()).unwrap();
+//! ```
+
+use crate::{
+    float::Float, frequency::Hertz, Biquad, Coefficients, DirectForm1, Errors, Section, Type,
+};
+
+/// A Direct Form 1 biquad together with the filter type, sample rate, cutoff frequency and Q value
+/// of its coefficients
+#[derive(Copy, Clone, Debug)]
+pub struct RetunableFilter<T> {
+    biquad: DirectForm1<T>,
+    filter: Type<T>,
+    fs: Hertz<T>,
+    f0: Hertz<T>,
+    q_value: T,
+}
+
+impl<T: Float> RetunableFilter<T>
+where
+    DirectForm1<T>: Biquad<T> + Section<Sample = T>,
+{
+    /// Creates the filter with coefficients from `Coefficients::from_params`, resulting in the
+    /// same errors
+    pub fn new(filter: Type<T>, fs: Hertz<T>, f0: Hertz<T>, q_value: T) -> Result<Self, Errors> {
+        let coeffs = Coefficients::<T>::from_params(filter, fs, f0, q_value)?;
+
+        Ok(RetunableFilter {
+            biquad: DirectForm1::<T>::from_coefficients(coeffs),
+            filter,
+            fs,
+            f0,
+            q_value,
+        })
+    }
+
+    /// The current Q value
+    pub fn q(&self) -> T {
+        self.q_value
+    }
+
+    /// The current cutoff or center frequency
+    pub fn frequency(&self) -> Hertz<T> {
+        self.f0
+    }
+
+    /// The current sampling frequency
+    pub fn sample_rate(&self) -> Hertz<T> {
+        self.fs
+    }
+
+    /// The current coefficients
+    pub fn coefficients(&self) -> Coefficients<T> {
+        self.biquad.coefficients()
+    }
+
+    /// Recomputes the coefficients with a new Q value, keeping the filter type, frequencies and
+    /// the internal state. The filter is left unchanged on an `Err()`.
+    pub fn set_q(&mut self, q_value: T) -> Result<(), Errors> {
+        let coeffs = Coefficients::<T>::from_params(self.filter, self.fs, self.f0, q_value)?;
+
+        self.biquad.update_coefficients(coeffs);
+        self.q_value = q_value;
+
+        Ok(())
+    }
+
+    /// Recomputes the coefficients with a new cutoff or center frequency, keeping the filter type,
+    /// Q value and the internal state, e.g. for a filter sweep. The filter is left unchanged on an
+    /// `Err()`.
+    pub fn set_frequency(&mut self, f0: Hertz<T>) -> Result<(), Errors> {
+        let coeffs = Coefficients::<T>::from_params(self.filter, self.fs, f0, self.q_value)?;
+
+        self.biquad.update_coefficients(coeffs);
+        self.f0 = f0;
+
+        Ok(())
+    }
+
+    /// Recomputes the coefficients for a new sampling frequency, keeping the filter type, cutoff
+    /// frequency and Q value, e.g. when the sample rate of the audio device changes. The internal
+    /// state is kept unless `reset_state` is set. Keeping the state avoids restarting from silence,
+    /// but as the samples in the state belong to the old sample rate the transition may click,
+    /// most audibly for resonant filters with a large state. The filter is left unchanged on an
+    /// `Err()`, e.g. if the cutoff is above half the new sampling frequency.
+    pub fn retune_sample_rate(&mut self, fs: Hertz<T>, reset_state: bool) -> Result<(), Errors> {
+        let coeffs = Coefficients::<T>::from_params(self.filter, fs, self.f0, self.q_value)?;
+
+        self.biquad.update_coefficients(coeffs);
+        self.fs = fs;
+        if reset_state {
+            self.biquad.reset_state();
+        }
+
+        Ok(())
+    }
+
+    /// A single iteration of the filter
+    pub fn run(&mut self, input: T) -> T {
+        self.biquad.run(input)
+    }
+
+    /// Set the internal state of the filter to 0 without allocation
+    pub fn reset_state(&mut self) {
+        self.biquad.reset_state();
+    }
+}
@@ -0,0 +1,88 @@
+//! # saturating
+//!
+//! An opt-in wrapper which applies a soft, `tanh` based, saturation to the output of a biquad.
+//! This is intended for "analog-ish" character and is deliberately kept apart from the linear
+//! biquad implementations, as the saturation makes the filter nonlinear.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 1.khz(), 10.hz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! // Saturate the output of a Direct Form 1 biquad with a drive of 2
+//! let mut biquad = SaturatingBiquad::<_, f32>::new(DirectForm1::<f32>::new(coeffs), 2.0).unwrap();
+//!
+//! let output = biquad.run(1.0);
+//! ```
+
+use crate::{float::Float, Biquad, Coefficients, Errors};
+
+/// A drive of 0 would divide by 0, and a negative drive would invert the output
+fn check_drive<T: Float>(drive: T) -> Result<(), Errors> {
+    if drive > T::ZERO && drive.is_finite() {
+        Ok(())
+    } else {
+        Err(Errors::InvalidDrive)
+    }
+}
+
+/// A biquad followed by a `tanh(drive * y) / drive` soft clipper. The small signal gain is unity,
+/// while the output is bounded by `1 / drive`.
+#[derive(Copy, Clone, Debug)]
+pub struct SaturatingBiquad<B, T> {
+    filter: B,
+    drive: T,
+}
+
+impl<T: Float, B: Biquad<T>> SaturatingBiquad<B, T> {
+    /// Wraps a biquad with a soft saturation stage. The drive must be larger than 0 and finite,
+    /// this will result in an `Err(Errors::InvalidDrive)` otherwise.
+    pub fn new(filter: B, drive: T) -> Result<Self, Errors> {
+        check_drive(drive)?;
+
+        Ok(SaturatingBiquad { filter, drive })
+    }
+
+    /// The current drive of the saturation stage
+    pub fn drive(&self) -> T {
+        self.drive
+    }
+
+    /// Updating of the drive, resulting in the same errors as `new`. The drive is left unchanged
+    /// on an `Err()`.
+    pub fn set_drive(&mut self, drive: T) -> Result<(), Errors> {
+        check_drive(drive)?;
+        self.drive = drive;
+
+        Ok(())
+    }
+
+    /// Returns the wrapped biquad
+    pub fn into_inner(self) -> B {
+        self.filter
+    }
+}
+
+impl<T: Float, B: Biquad<T>> Biquad<T> for SaturatingBiquad<B, T> {
+    fn run(&mut self, input: T) -> T {
+        T::tanh(self.drive * self.filter.run(input)) / self.drive
+    }
+
+    fn update_coefficients(&mut self, new_coefficients: Coefficients<T>) {
+        self.filter.update_coefficients(new_coefficients);
+    }
+
+    fn replace_coefficients(&mut self, new_coefficients: Coefficients<T>) -> Coefficients<T> {
+        self.filter.replace_coefficients(new_coefficients)
+    }
+
+    fn reset_state(&mut self) {
+        self.filter.reset_state();
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.filter.latency_samples()
+    }
+}
@@ -0,0 +1,118 @@
+//! # stream
+//!
+//! Streaming of raw PCM from a `std::io::Read` source through a biquad into a `std::io::Write`
+//! sink, e.g. for command line tools filtering stdin into stdout. The samples are processed in
+//! small chunks, so streams of any length are filtered without buffering them. Requires the `std`
+//! feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! let coeffs = Coefficients::<f32>::from_params(Type::LowPass, 48.khz(), 1.khz(), Q_BUTTERWORTH_F32).unwrap();
+//!
+//! let input: Vec<u8> = [0i16, 16384, -16384, 0].iter().flat_map(|s| s.to_le_bytes()).collect();
+//! let mut output = Vec::new();
+//!
+//! filter_stream(coeffs, &input[..], &mut output, PcmFormat::I16Le).unwrap();
+//!
+//! assert_eq!(output.len(), input.len());
+//! ```
+
+use crate::{Coefficients, DirectForm2Transposed};
+use core::convert::TryInto;
+use libm::roundf;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+/// Length in bytes of the chunks read from the source, a multiple of all sample lengths, where the
+/// shortest sample is 2 bytes long
+const BUFFER_LEN: usize = 4096;
+
+/// Encodings of raw single channel PCM
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PcmFormat {
+    /// 32 bit little endian floats, with full scale at `[-1, 1]`
+    F32Le,
+    /// 16 bit little endian signed integers, with full scale at `[-32768, 32767]`
+    I16Le,
+}
+
+impl PcmFormat {
+    /// The length in bytes of a sample
+    pub fn sample_len(self) -> usize {
+        match self {
+            PcmFormat::F32Le => 4,
+            PcmFormat::I16Le => 2,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            PcmFormat::F32Le => f32::from_le_bytes(bytes.try_into().unwrap()),
+            PcmFormat::I16Le => i16::from_le_bytes(bytes.try_into().unwrap()) as f32 / 32768.0,
+        }
+    }
+
+    fn encode(self, sample: f32, bytes: &mut [u8]) {
+        match self {
+            PcmFormat::F32Le => bytes.copy_from_slice(&sample.to_le_bytes()),
+            PcmFormat::I16Le => {
+                // The float to integer cast saturates at the limits of `i16`
+                let sample = roundf(sample * 32768.0) as i16;
+                bytes.copy_from_slice(&sample.to_le_bytes());
+            }
+        }
+    }
+}
+
+/// Filters the PCM samples read from `reader` with `coeffs` and writes them in the same format to
+/// `writer` until the end of the stream. Integer samples saturate at full scale. Results in an
+/// `Err()` with `ErrorKind::UnexpectedEof` if the stream ends within a sample, and passes on the
+/// errors of `reader` and `writer`.
+pub fn filter_stream<R: Read, W: Write>(
+    coeffs: Coefficients<f32>,
+    mut reader: R,
+    mut writer: W,
+    format: PcmFormat,
+) -> Result<()> {
+    let mut biquad = DirectForm2Transposed::<f32>::new(coeffs);
+    let sample_len = format.sample_len();
+    let mut buf = [0u8; BUFFER_LEN];
+    let mut samples = [0.0f32; BUFFER_LEN / 2];
+    let mut filled = 0;
+
+    loop {
+        let read = match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        filled += read;
+
+        // Only whole samples are filtered, the bytes of a partial sample are kept for the next read
+        let whole = filled - filled % sample_len;
+        let samples = &mut samples[..whole / sample_len];
+        for (sample, bytes) in samples.iter_mut().zip(buf.chunks_exact(sample_len)) {
+            *sample = format.decode(bytes);
+        }
+        biquad.run_block(samples);
+        for (&sample, bytes) in samples.iter().zip(buf.chunks_exact_mut(sample_len)) {
+            format.encode(sample, bytes);
+        }
+        writer.write_all(&buf[..whole])?;
+
+        buf.copy_within(whole..filled, 0);
+        filled -= whole;
+    }
+
+    if filled != 0 {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "the stream ends within a sample",
+        ));
+    }
+
+    writer.flush()
+}
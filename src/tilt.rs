@@ -0,0 +1,93 @@
+//! # tilt
+//!
+//! A tilt EQ, a tonal balance control with a single knob which boosts one end of the spectrum
+//! while cutting the other by the same amount, pivoting around a center frequency.
+//!
+//! # Examples
+//!
+//! ```
+//! use biquad::*;
+//!
+//! // Tilt by 3 dB towards the highs around 1 kHz
+//! let mut tilt = TiltEq::<f32>::new(48.khz(), 1.khz(), 3.0).unwrap();
+//!
+//! let output = tilt.run(1.0);
+//!
+//! // Back to a flat response
+//! tilt.set_tilt_db(0.0).unwrap();
+//! ```
+
+use crate::float::Float;
+use crate::{Biquad, Coefficients, Decibels, DirectForm1, Errors, Hertz, Type, Q_BUTTERWORTH_F64};
+
+/// A low shelf and a high shelf at the pivot frequency with opposite gains of half the tilt,
+/// giving `-tilt_db / 2` at DC, `tilt_db / 2` at Nyquist and 0 dB at the pivot
+#[derive(Copy, Clone, Debug)]
+pub struct TiltEq<T> {
+    low: DirectForm1<T>,
+    high: DirectForm1<T>,
+    fs: Hertz<T>,
+    pivot: Hertz<T>,
+    tilt_db: T,
+}
+
+impl<T: Float> TiltEq<T> {
+    /// Creates a tilt EQ pivoting around `pivot`, where a positive `tilt_db` boosts the highs
+    /// and cuts the lows. The pivot must be smaller than half the sampling frequency.
+    pub fn new(
+        fs: Hertz<T>,
+        pivot: Hertz<T>,
+        tilt_db: impl Into<Decibels<T>>,
+    ) -> Result<Self, Errors> {
+        let tilt_db = tilt_db.into().db();
+        let (low, high) = TiltEq::<T>::shelves(fs, pivot, tilt_db)?;
+
+        Ok(TiltEq {
+            low: DirectForm1::<T>::new(low),
+            high: DirectForm1::<T>::new(high),
+            fs,
+            pivot,
+            tilt_db,
+        })
+    }
+
+    fn shelves(
+        fs: Hertz<T>,
+        pivot: Hertz<T>,
+        tilt_db: T,
+    ) -> Result<(Coefficients<T>, Coefficients<T>), Errors> {
+        let q = T::from_f64(Q_BUTTERWORTH_F64);
+        let low = Coefficients::<T>::from_params(Type::LowShelf(-tilt_db / T::TWO), fs, pivot, q)?;
+        let high = Coefficients::<T>::from_params(Type::HighShelf(tilt_db / T::TWO), fs, pivot, q)?;
+
+        Ok((low, high))
+    }
+
+    /// The current tilt in dB
+    pub fn tilt_db(&self) -> T {
+        self.tilt_db
+    }
+
+    /// Updating of the tilt, keeping the internal states
+    pub fn set_tilt_db(&mut self, tilt_db: impl Into<Decibels<T>>) -> Result<(), Errors> {
+        let tilt_db = tilt_db.into().db();
+        let (low, high) = TiltEq::<T>::shelves(self.fs, self.pivot, tilt_db)?;
+
+        self.low.update_coefficients(low);
+        self.high.update_coefficients(high);
+        self.tilt_db = tilt_db;
+
+        Ok(())
+    }
+
+    /// A single iteration of the tilt EQ, running the input through both shelves
+    pub fn run(&mut self, input: T) -> T {
+        self.high.run(self.low.run(input))
+    }
+
+    /// Set the internal state of both shelves to 0 without allocation
+    pub fn reset_state(&mut self) {
+        self.low.reset_state();
+        self.high.reset_state();
+    }
+}